@@ -0,0 +1,353 @@
+use graphqlgen_schema::ast::{
+    Comment, Comments, Definition, DirectiveDef, Document, EnumDef, EnumValue, Extension, Field,
+    InputObjectDef, InputValue, RootOperationTypeDef, ScalarDef, SchemaDef, Span, TypeDef,
+    UnionDef,
+};
+
+/// A comment read by the parser, with what [`attach_comments`] needs to find
+/// the node it belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trivia {
+    pub comment: Comment,
+    /// `false` for a comment following a token on the same line.
+    pub own_line: bool,
+    /// The end offset of the token before the comment.
+    pub after: usize,
+}
+
+/// A node comments can be attached to.
+trait Commented {
+    fn span(&self) -> Span;
+
+    fn comments_mut(&mut self) -> &mut Comments;
+
+    /// The nodes directly inside this one that take comments, in source order.
+    fn children_mut(&mut self) -> Vec<&mut dyn Commented> {
+        Vec::new()
+    }
+
+    /// Whether the node has braces to keep inner comments in.
+    fn has_body(&self) -> bool {
+        false
+    }
+}
+
+impl Commented for TypeDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Commented> {
+        self.fields
+            .iter_mut()
+            .map(|child| child as &mut dyn Commented)
+            .collect()
+    }
+
+    fn has_body(&self) -> bool {
+        true
+    }
+}
+
+impl Commented for InputObjectDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Commented> {
+        self.fields
+            .iter_mut()
+            .map(|child| child as &mut dyn Commented)
+            .collect()
+    }
+
+    fn has_body(&self) -> bool {
+        true
+    }
+}
+
+impl Commented for Field {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Commented> {
+        self.arguments
+            .iter_mut()
+            .flatten()
+            .map(|child| child as &mut dyn Commented)
+            .collect()
+    }
+}
+
+impl Commented for InputValue {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+}
+
+impl Commented for ScalarDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+}
+
+impl Commented for UnionDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+}
+
+impl Commented for EnumDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Commented> {
+        self.values
+            .iter_mut()
+            .map(|child| child as &mut dyn Commented)
+            .collect()
+    }
+
+    fn has_body(&self) -> bool {
+        true
+    }
+}
+
+impl Commented for EnumValue {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+}
+
+impl Commented for SchemaDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Commented> {
+        self.operation_types
+            .iter_mut()
+            .map(|child| child as &mut dyn Commented)
+            .collect()
+    }
+
+    fn has_body(&self) -> bool {
+        true
+    }
+}
+
+impl Commented for RootOperationTypeDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+}
+
+impl Commented for DirectiveDef {
+    fn span(&self) -> Span {
+        self.span
+    }
+
+    fn comments_mut(&mut self) -> &mut Comments {
+        &mut self.comments
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn Commented> {
+        self.arguments
+            .iter_mut()
+            .flatten()
+            .map(|child| child as &mut dyn Commented)
+            .collect()
+    }
+}
+
+fn definition_node(definition: &mut Definition) -> &mut dyn Commented {
+    match definition {
+        Definition::Type(def) | Definition::Interface(def) => def,
+        Definition::Input(def) => def,
+        Definition::Scalar(def) => def,
+        Definition::Union(def) => def,
+        Definition::Enum(def) => def,
+        Definition::Schema(def) => def,
+        Definition::Directive(def) => def,
+        Definition::Extension(ext) => match ext {
+            Extension::Type(def) | Extension::Interface(def) => def,
+            Extension::Input(def) => def,
+            Extension::Scalar(def) => def,
+            Extension::Union(def) => def,
+            Extension::Enum(def) => def,
+            Extension::Schema(def) => def,
+        },
+    }
+}
+
+/// Attaches each comment to the node it belongs to:
+///
+/// - a comment at the end of a node's last line is that node's trailing
+///   comment,
+/// - any other comment is a leading comment of the next node at the same
+///   depth; if there is none, it is an inner comment of the enclosing type,
+///   enum or schema, or a leading comment of any other enclosing node,
+/// - comments after the last definition are kept on the document.
+///
+/// Expects `comments` and the document's definitions in source order, as
+/// read by [`Parser::with_comments`](super::parser::Parser::with_comments).
+pub fn attach_comments(document: &mut Document, comments: Vec<Trivia>) {
+    for trivia in comments {
+        // Definitions ending before the token the comment follows cannot
+        // hold it.
+        let first: usize = document
+            .definitions
+            .partition_point(|def| def.span().end.offset < trivia.after);
+        let nodes: Vec<&mut dyn Commented> = document.definitions[first..]
+            .iter_mut()
+            .map(definition_node)
+            .collect();
+
+        if let Some(rest) = attach(nodes, trivia) {
+            document.comments.push(rest.comment);
+        }
+    }
+}
+
+/// Attaches `trivia` to one of `nodes`, siblings in source order, or to a node
+/// inside them. Returns it if it comes after all of them.
+fn attach(nodes: Vec<&mut dyn Commented>, trivia: Trivia) -> Option<Trivia> {
+    let offset: usize = trivia.comment.span.start.offset;
+
+    for node in nodes {
+        let span: Span = node.span();
+
+        if !trivia.own_line && span.end.offset == trivia.after {
+            node.comments_mut().trailing = Some(trivia.comment);
+            return None;
+        }
+        if span.start.offset > offset {
+            node.comments_mut().leading.push(trivia.comment);
+            return None;
+        }
+        if span.end.offset > offset {
+            if let Some(rest) = attach(node.children_mut(), trivia) {
+                if node.has_body() {
+                    node.comments_mut().inner.push(rest.comment);
+                } else {
+                    node.comments_mut().leading.push(rest.comment);
+                }
+            }
+            return None;
+        }
+    }
+
+    Some(trivia)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::parse::parse_document;
+
+    fn parse(source: &str) -> Document {
+        parse_document(&mut Parser::with_comments(source))
+            .into_result()
+            .unwrap()
+    }
+
+    fn texts(comments: &[Comment]) -> Vec<&str> {
+        comments.iter().map(|c| c.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_attaches_leading_and_trailing_comments() {
+        let document: Document = parse(
+            "# License\n\n# A user\n\"Doc\"\ntype User { # opens\n  # the id\n  id: ID! # required\n  name(\n    # first\n    first: Int # count\n  ): String\n} # done\n",
+        );
+        let Definition::Type(user) = &document.definitions[0] else {
+            panic!("Expected TypeDef");
+        };
+
+        assert_eq!(texts(&user.comments.leading), vec![" License", " A user"]);
+        assert!(user.comments.leading[0].blank_line_after);
+        assert!(!user.comments.leading[1].blank_line_after);
+        assert_eq!(user.comments.trailing.as_ref().unwrap().text, " done");
+
+        let id: &Field = &user.fields[0];
+        assert_eq!(texts(&id.comments.leading), vec![" opens", " the id"]);
+        assert_eq!(id.comments.trailing.as_ref().unwrap().text, " required");
+
+        let first: &InputValue = &user.fields[1].arguments.as_ref().unwrap()[0];
+        assert_eq!(texts(&first.comments.leading), vec![" first"]);
+        assert_eq!(first.comments.trailing.as_ref().unwrap().text, " count");
+    }
+
+    #[test]
+    fn test_attaches_inner_and_document_comments() {
+        let document: Document =
+            parse("enum Role {\n  ADMIN\n  # more to come\n}\nunion U = A\n  # B?\n  | C\n# end");
+
+        let Definition::Enum(role) = &document.definitions[0] else {
+            panic!("Expected EnumDef");
+        };
+        assert_eq!(texts(&role.comments.inner), vec![" more to come"]);
+        assert!(role.values[0].comments.is_empty());
+
+        let Definition::Union(union_def) = &document.definitions[1] else {
+            panic!("Expected UnionDef");
+        };
+        assert_eq!(texts(&union_def.comments.leading), vec![" B?"]);
+        assert_eq!(texts(&document.comments), vec![" end"]);
+    }
+
+    #[test]
+    fn test_comments_are_dropped_by_default() {
+        let document: Document = parse_document(&mut Parser::new("# a\nscalar A # b"))
+            .into_result()
+            .unwrap();
+
+        let Definition::Scalar(scalar) = &document.definitions[0] else {
+            panic!("Expected ScalarDef");
+        };
+        assert!(scalar.comments.is_empty());
+    }
+}
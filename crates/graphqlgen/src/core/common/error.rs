@@ -0,0 +1,4 @@
+pub use graphqlgen_schema::diagnostic::{Diagnostic, Label, Severity};
+
+/// The result type used throughout the lexer and parser.
+pub type Result<T, E = Diagnostic> = std::result::Result<T, E>;
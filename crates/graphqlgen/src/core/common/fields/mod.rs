@@ -1,2 +1,3 @@
-pub mod parse_field_arguments;
+pub mod parse_arguments_definition;
 pub mod parse_fields;
+pub mod parse_input_fields;
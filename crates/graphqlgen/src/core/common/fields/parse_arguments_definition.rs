@@ -0,0 +1,171 @@
+use crate::core::common::error::Result;
+use crate::core::common::{
+    parse::{
+        directives::{deprecation, parse_const_directives},
+        type_ref::parse_type_ref,
+        value::parse_const_value,
+    },
+    parser::Parser,
+    token::Token,
+};
+use graphqlgen_schema::ast::{Comments, InputValue, Value};
+
+/// Parses `(name: Type = default @directives, ...)` with the parser on the
+/// opening paren.
+pub fn parse_arguments_definition(parser: &mut Parser) -> Result<Vec<InputValue>> {
+    let mut args: Vec<InputValue> = Vec::new();
+    parser.expect(Token::ParenOpen)?;
+
+    while !parser.at_end() && !parser.at(&Token::ParenClose) {
+        args.push(parse_input_value(parser)?);
+    }
+
+    parser.expect(Token::ParenClose)?;
+    Ok(args)
+}
+
+/// Parses one `"description" name: Type = default @directives` argument or
+/// input field.
+pub fn parse_input_value(parser: &mut Parser) -> Result<InputValue> {
+    let description: Option<String> = parser.take_description();
+
+    let start: usize = parser.position();
+    let name: String = parser.expect_field_name()?;
+    parser.expect(Token::Colon)?;
+    let value_type = parse_type_ref(parser)?;
+
+    let default_value: Option<Value> = if parser.eat(&Token::Equals) {
+        Some(parse_const_value(parser)?)
+    } else {
+        None
+    };
+    let directives = parse_const_directives(parser)?;
+
+    Ok(InputValue {
+        name,
+        value_type,
+        default_value,
+        deprecation: deprecation(&directives),
+        directives: if directives.is_empty() {
+            None
+        } else {
+            Some(directives)
+        },
+        description,
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
+    use graphqlgen_schema::ast::TypeRef;
+
+    #[test]
+    fn test_parse_arguments_with_defaults() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::ParenOpen,
+            Token::Description("How many".into()),
+            Token::Name("limit"),
+            Token::Colon,
+            Token::Name("Int"),
+            Token::Equals,
+            Token::Int(10),
+            Token::Name("after"),
+            Token::Colon,
+            Token::Name("ID"),
+            Token::Bang,
+            Token::ParenClose,
+        ]));
+
+        let args = parse_arguments_definition(&mut parser).unwrap();
+        assert_eq!(parser.position(), 12);
+        assert_eq!(args.len(), 2);
+
+        assert_eq!(args[0].name, "limit");
+        assert_eq!(args[0].description.as_deref(), Some("How many"));
+        assert_eq!(args[0].default_value, Some(Value::Int(10)));
+
+        assert_eq!(args[1].name, "after");
+        assert!(matches!(args[1].value_type, TypeRef::NonNull(_)));
+        assert!(args[1].default_value.is_none());
+    }
+
+    #[test]
+    fn test_parse_argument_directives() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::ParenOpen,
+            Token::Name("first"),
+            Token::Colon,
+            Token::Name("Int"),
+            Token::Equals,
+            Token::Int(10),
+            Token::At,
+            Token::Name("deprecated"),
+            Token::Name("limit"),
+            Token::Colon,
+            Token::Name("Int"),
+            Token::At,
+            Token::Name("range"),
+            Token::ParenOpen,
+            Token::Name("max"),
+            Token::Colon,
+            Token::Int(100),
+            Token::ParenClose,
+            Token::ParenClose,
+        ]));
+
+        let args = parse_arguments_definition(&mut parser).unwrap();
+        assert!(parser.at_end());
+
+        assert_eq!(args[0].default_value, Some(Value::Int(10)));
+        assert_eq!(args[0].directives.as_ref().unwrap()[0].name, "deprecated");
+        assert_eq!(args[1].directives.as_ref().unwrap()[0].name, "range");
+        assert!(args[1].default_value.is_none());
+    }
+
+    #[test]
+    fn test_parse_arguments_missing_default() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::ParenOpen,
+            Token::Name("limit"),
+            Token::Colon,
+            Token::Name("Int"),
+            Token::Equals,
+            Token::ParenClose,
+        ]));
+
+        let result = parse_arguments_definition(&mut parser);
+        assert!(result
+            .unwrap_err()
+            .message
+            .starts_with("Expected a value, found `)`"));
+    }
+
+    #[test]
+    fn test_parse_arguments_variable_in_directive() {
+        let mut parser = Parser::new("(limit: Int @range(max: $max))");
+
+        let result = parse_arguments_definition(&mut parser);
+        assert_eq!(
+            result.unwrap_err().message,
+            "Variables are not allowed in constant values"
+        );
+    }
+
+    #[test]
+    fn test_parse_arguments_unclosed() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::ParenOpen,
+            Token::Name("limit"),
+            Token::Colon,
+            Token::Name("Int"),
+        ]));
+
+        let result = parse_arguments_definition(&mut parser);
+        assert!(result.is_err());
+    }
+}
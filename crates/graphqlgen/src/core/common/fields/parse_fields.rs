@@ -1,68 +1,66 @@
+use crate::core::common::error::Result;
 use crate::core::common::{
     parse::{
-        description::take_description, directives::parse_directives, expect::expect_token,
+        directives::{deprecation, parse_const_directives},
+        expect::describe,
         type_ref::parse_type_ref,
     },
+    parser::Parser,
     token::Token,
 };
-use anyhow::{anyhow, Result};
-use graphqlgen_schema::ast::{Field, InputValue};
+use graphqlgen_schema::ast::{Comments, Field, InputValue};
 
-use super::parse_field_arguments::parse_field_arguments;
+use super::parse_arguments_definition::parse_arguments_definition;
 
-pub fn parse_fields(tokens: &[Token], index: &mut usize) -> Result<Vec<Field>> {
+pub fn parse_fields(parser: &mut Parser) -> Result<Vec<Field>> {
     let mut fields: Vec<Field> = Vec::new();
 
-    while *index < tokens.len() {
-        let description: Option<String> = take_description(tokens, index);
+    while !parser.at_end() {
+        let description: Option<String> = parser.take_description();
+        let start: usize = parser.position();
 
-        match &tokens[*index] {
-            Token::BraceClose => {
-                *index += 1;
+        match parser.peek() {
+            Some(Token::BraceClose) => {
+                parser.bump();
                 break;
             }
 
-            Token::Name(field_name) => {
-                let name: String = field_name.clone();
-                *index += 1;
+            Some(Token::Name(_) | Token::Boolean(_) | Token::Null) => {
+                let name: String = parser.expect_field_name()?;
 
-                let arguments: Option<Vec<Field>> = if tokens.get(*index) == Some(&Token::ParenOpen)
-                {
-                    Some(parse_field_arguments(tokens, index)?)
+                let arguments: Option<Vec<InputValue>> = if parser.at(&Token::ParenOpen) {
+                    Some(parse_arguments_definition(parser)?)
                 } else {
                     None
                 };
 
-                expect_token(tokens, index, Token::Colon)?;
-                let (field_type, consumed) = parse_type_ref(&tokens[*index..])?;
-                *index += consumed;
+                parser.expect(Token::Colon)?;
+                let field_type = parse_type_ref(parser)?;
 
-                let directives = parse_directives(tokens, index)?;
+                let directives = parse_const_directives(parser)?;
 
                 fields.push(Field {
                     name,
                     field_type,
-                    arguments: arguments.map(|args| {
-                        args.into_iter()
-                            .map(|arg| InputValue {
-                                name: arg.name,
-                                value_type: arg.field_type,
-                                default_value: None,
-                                description: None,
-                            })
-                            .collect()
-                    }),
+                    arguments,
+                    deprecation: deprecation(&directives),
                     directives: if directives.is_empty() {
                         None
                     } else {
                         Some(directives)
                     },
                     description,
+                    span: parser.span_from(start),
+                    comments: Comments::default(),
                 });
             }
 
-            other => {
-                return Err(anyhow!("Unexpected token in fields: {:?}", other));
+            Some(other) => {
+                return Err(parser.unexpected(format!("Unexpected {} in fields", describe(other))));
+            }
+
+            None => {
+                return Err(parser.unexpected("Unexpected end of input in fields"));
             }
         }
     }
@@ -0,0 +1,58 @@
+use crate::core::common::error::Result;
+use crate::core::common::{parser::Parser, token::Token};
+use graphqlgen_schema::ast::InputValue;
+
+use super::parse_arguments_definition::parse_input_value;
+
+/// Parses `{ name: Type = default @directives ... }` with the parser on the
+/// opening brace.
+pub fn parse_input_fields(parser: &mut Parser) -> Result<Vec<InputValue>> {
+    let mut fields: Vec<InputValue> = Vec::new();
+    parser.expect(Token::BraceOpen)?;
+
+    while !parser.at_end() && !parser.at(&Token::BraceClose) {
+        fields.push(parse_input_value(parser)?);
+    }
+
+    parser.expect(Token::BraceClose)?;
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphqlgen_schema::ast::{TypeRef, Value};
+
+    #[test]
+    fn test_parse_input_fields() {
+        let mut parser = Parser::new(
+            r#"{
+                "Case sensitive"
+                name: String = "a" @deprecated(reason: "Use `query`.")
+                limit: Int! = 10
+                tags: [String!]
+            }"#,
+        );
+
+        let fields: Vec<InputValue> = parse_input_fields(&mut parser).unwrap();
+        assert!(parser.at_end());
+        assert_eq!(fields.len(), 3);
+
+        assert_eq!(fields[0].description.as_deref(), Some("Case sensitive"));
+        assert_eq!(fields[0].default_value, Some(Value::String("a".into())));
+        assert_eq!(
+            fields[0].deprecation.as_ref().unwrap().reason,
+            "Use `query`."
+        );
+        assert_eq!(fields[1].default_value, Some(Value::Int(10)));
+        assert!(matches!(fields[1].value_type, TypeRef::NonNull(_)));
+        assert!(fields[2].default_value.is_none());
+    }
+
+    #[test]
+    fn test_parse_input_fields_rejects_arguments() {
+        let mut parser = Parser::new("{ posts(first: Int): [Post] }");
+
+        assert!(parse_input_fields(&mut parser).is_err());
+    }
+}
@@ -1,166 +1,833 @@
-use super::token::Token;
-use anyhow::{bail, Result};
+use std::borrow::Cow;
 
+use super::error::{Diagnostic, Result};
+use super::token::{SpannedToken, Token};
+use graphqlgen_schema::ast::{Pos, Span};
+
+/// Splits GraphQL source into tokens. Scans bytes and hands out slices of the
+/// source, so names, and strings without escapes, are never copied. Tokens
+/// are produced lazily through [`Lexer::next_token`] or the `Iterator` impl.
 pub struct Lexer<'a> {
-    chars: std::str::Chars<'a>,
-    peeked: Option<char>,
+    source: &'a str,
+    bytes: &'a [u8],
+    pos: Pos,
+    comments: bool,
 }
 
 impl<'a> Lexer<'a> {
-    pub fn new(src: &'a str) -> Self {
-        let mut chars = src.chars();
-        let peeked: Option<char> = chars.next();
-        Self { chars, peeked }
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            source,
+            bytes: source.as_bytes(),
+            pos: Pos {
+                line: 1,
+                column: 1,
+                offset: 0,
+            },
+            comments: false,
+        }
+    }
+
+    /// Emits `#` comments as [`Token::Comment`] instead of skipping them.
+    pub fn with_comments(mut self) -> Self {
+        self.comments = true;
+        self
+    }
+
+    /// Advances one byte. Columns count characters, so UTF-8 continuation
+    /// bytes do not move the column.
+    fn bump(&mut self) -> Option<u8> {
+        let current: u8 = *self.bytes.get(self.pos.offset)?;
+        self.pos.offset += 1;
+        if current == b'\n' {
+            self.pos.line += 1;
+            self.pos.column = 1;
+        } else if current & 0xC0 != 0x80 {
+            self.pos.column += 1;
+        }
+        Some(current)
+    }
+
+    /// Advances past the whole character at the current position.
+    fn bump_char(&mut self) -> Option<char> {
+        let c: char = self.source[self.pos.offset..].chars().next()?;
+        for _ in 0..c.len_utf8() {
+            self.bump();
+        }
+        Some(c)
     }
 
-    fn bump(&mut self) -> Option<char> {
-        let current: Option<char> = self.peeked;
-        self.peeked = self.chars.next();
-        current
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos.offset).copied()
     }
 
-    fn peek(&self) -> Option<char> {
-        self.peeked
+    /// Returns `true` if the remaining input starts with `text`.
+    fn lookahead(&self, text: &str) -> bool {
+        self.bytes[self.pos.offset..].starts_with(text.as_bytes())
+    }
+
+    fn slice(&self, start: Pos) -> &'a str {
+        &self.source[start.offset..self.pos.offset]
+    }
+
+    fn error(&self, start: Pos, code: &str, message: impl Into<String>) -> Diagnostic {
+        Diagnostic::error(code, message).with_primary(Span::new(start, self.pos), "")
     }
 
     fn skip_whitespace(&mut self) {
-        while matches!(self.peek(), Some(c) if c.is_whitespace() || c == ',') {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\n' | b'\r' | b',') => {
+                    self.bump();
+                }
+                Some(b'#') if !self.comments => {
+                    self.read_comment();
+                }
+                _ if self.lookahead("\u{FEFF}") => {
+                    self.bump_char();
+                }
+                _ => break,
+            }
+        }
+    }
+
+    /// Reads a `#` comment up to the end of its line, returning its text
+    /// after the `#`.
+    fn read_comment(&mut self) -> &'a str {
+        self.bump();
+        let start: Pos = self.pos;
+        while !matches!(self.peek(), None | Some(b'\n' | b'\r')) {
             self.bump();
         }
+        self.slice(start)
     }
 
-    pub fn next_token(&mut self) -> Result<Token> {
+    pub fn next_token(&mut self) -> Result<SpannedToken<'a>> {
         self.skip_whitespace();
-        match self.bump() {
-            Some('{') => Ok(Token::BraceOpen),
-            Some('}') => Ok(Token::BraceClose),
-            Some('(') => Ok(Token::ParenOpen),
-            Some(')') => Ok(Token::ParenClose),
-            Some('[') => Ok(Token::BracketOpen),
-            Some(']') => Ok(Token::BracketClose),
-            Some(':') => Ok(Token::Colon),
-            Some('=') => Ok(Token::Equals),
-            Some('@') => Ok(Token::At),
-            Some('$') => Ok(Token::Dollar),
-            Some('!') => Ok(Token::Bang),
-            Some('|') => Ok(Token::Pipe),
-            Some('&') => Ok(Token::Ampersand),
-            Some(',') => Ok(Token::Comma),
-            Some('.') => {
-                if self.peek() == Some('.') {
+        let start: Pos = self.pos;
+        let token: Token<'a> = self.read_token(start)?;
+
+        Ok(SpannedToken {
+            token,
+            span: Span::new(start, self.pos),
+        })
+    }
+
+    fn read_token(&mut self, start: Pos) -> Result<Token<'a>> {
+        let Some(first) = self.peek() else {
+            return Ok(Token::EOF);
+        };
+
+        // Only reached with comments enabled, see `skip_whitespace`.
+        if first == b'#' {
+            return Ok(Token::Comment(self.read_comment()));
+        }
+
+        let punctuator: Option<Token<'a>> = match first {
+            b'{' => Some(Token::BraceOpen),
+            b'}' => Some(Token::BraceClose),
+            b'(' => Some(Token::ParenOpen),
+            b')' => Some(Token::ParenClose),
+            b'[' => Some(Token::BracketOpen),
+            b']' => Some(Token::BracketClose),
+            b':' => Some(Token::Colon),
+            b'=' => Some(Token::Equals),
+            b'@' => Some(Token::At),
+            b'$' => Some(Token::Dollar),
+            b'!' => Some(Token::Bang),
+            b'|' => Some(Token::Pipe),
+            b'&' => Some(Token::Ampersand),
+            b',' => Some(Token::Comma),
+            _ => None,
+        };
+        if let Some(token) = punctuator {
+            self.bump();
+            return Ok(token);
+        }
+
+        match first {
+            b'.' => {
+                self.bump();
+                if self.peek() == Some(b'.') {
                     self.bump();
-                    if self.bump() == Some('.') {
+                    // The third character is left for the next token, so a
+                    // multibyte one is never split.
+                    if self.peek() == Some(b'.') {
+                        self.bump();
                         Ok(Token::Ellipsis)
                     } else {
-                        bail!("Unexpected character after '..'")
+                        Err(self
+                            .error(
+                                start,
+                                "unexpected-character",
+                                "Unexpected character after '..'",
+                            )
+                            .with_help("did you mean `...`?"))
                     }
                 } else {
-                    bail!("Unexpected character: '.'")
+                    Err(self
+                        .error(start, "unexpected-character", "Unexpected character: '.'")
+                        .with_help("did you mean `...`?"))
                 }
             }
-            Some('"') => {
-                if self.peek() == Some('"') {
-                    self.bump();
-                    if self.peek() == Some('"') {
+            b'"' => {
+                if self.lookahead("\"\"\"") {
+                    for _ in 0..3 {
                         self.bump();
-                        return self.read_block_string();
-                    } else {
-                        bail!("Unexpected character after '\"'")
                     }
+                    self.read_block_string(start)
                 } else {
-                    self.read_string()
+                    self.bump();
+                    self.read_string(start)
                 }
             }
-
-            Some(c) if c.is_alphabetic() || c == '_' => self.read_name_or_keyword(c),
-            Some(c) if c.is_digit(10) || c == '-' => self.read_number(c),
-            Some('#') => {
-                while let Some(c) = self.bump() {
-                    if c == '\n' {
-                        break;
-                    }
-                }
-                self.next_token()
+            c if c.is_ascii_alphabetic() || c == b'_' => Ok(self.read_name_or_keyword(start)),
+            c if c.is_ascii_digit() || c == b'-' => self.read_number(start),
+            _ => {
+                let c: char = self.bump_char().unwrap_or_default();
+                Err(self.error(
+                    start,
+                    "unexpected-character",
+                    format!("Unexpected character: '{}'", c),
+                ))
             }
-            None => Ok(Token::EOF),
-            Some(c) => bail!("Unexpected character: '{}'", c),
         }
     }
 
-    fn read_string(&mut self) -> Result<Token> {
-        let mut s = String::new();
+    fn read_string(&mut self, start: Pos) -> Result<Token<'a>> {
+        // Stays `None`, and the token borrows the source, until an escape
+        // sequence has to be decoded.
+        let mut decoded: Option<String> = None;
+        let mut run_start: usize = self.pos.offset;
+
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    let run: &'a str = &self.source[run_start..self.pos.offset];
+                    self.bump();
+                    return Ok(Token::String(match decoded {
+                        Some(mut s) => {
+                            s.push_str(run);
+                            Cow::Owned(s)
+                        }
+                        None => Cow::Borrowed(run),
+                    }));
+                }
+                Some(b'\\') => {
+                    let escape_start: Pos = self.pos;
+                    self.bump();
+                    let c: char = self.read_escape(escape_start)?;
 
-        while let Some(c) = self.bump() {
-            if c == '"' {
-                return Ok(Token::String(s));
+                    let s: &mut String = decoded.get_or_insert_with(String::new);
+                    s.push_str(&self.source[run_start..escape_start.offset]);
+                    s.push(c);
+                    run_start = self.pos.offset;
+                }
+                Some(b'\n' | b'\r') | None => {
+                    return Err(self
+                        .error(start, "unterminated-string", "Unterminated string")
+                        .with_help(
+                            "add a closing `\"`, or use a block string (`\"\"\"`) for text \
+                             spanning several lines",
+                        ))
+                }
+                Some(_) => {
+                    self.bump();
+                }
             }
-            s.push(c);
         }
-        bail!("Unterminated string")
     }
 
-    fn read_name_or_keyword(&mut self, first: char) -> Result<Token> {
-        let mut name = String::new();
-        name.push(first);
-        while matches!(self.peek(), Some(c) if c.is_alphanumeric() || c == '_') {
-            name.push(self.bump().unwrap());
+    /// Reads the escape sequence after a `\` in a string.
+    fn read_escape(&mut self, start: Pos) -> Result<char> {
+        match self.peek() {
+            Some(b'"') => Ok('"'),
+            Some(b'\\') => Ok('\\'),
+            Some(b'/') => Ok('/'),
+            Some(b'b') => Ok('\u{8}'),
+            Some(b'f') => Ok('\u{c}'),
+            Some(b'n') => Ok('\n'),
+            Some(b'r') => Ok('\r'),
+            Some(b't') => Ok('\t'),
+            Some(b'u') => {
+                self.bump();
+                return self.read_unicode_escape(start);
+            }
+            Some(_) => {
+                let c: char = self.bump_char().unwrap_or_default();
+                return Err(self
+                    .error(
+                        start,
+                        "invalid-escape",
+                        format!("Invalid escape sequence '\\{}'", c),
+                    )
+                    .with_help("valid escapes are \\\" \\\\ \\/ \\b \\f \\n \\r \\t and \\u"));
+            }
+            None => {
+                return Err(self
+                    .error(start, "unterminated-string", "Unterminated string")
+                    .with_help("add a closing `\"`"))
+            }
         }
+        .inspect(|_| {
+            self.bump();
+        })
+    }
 
-        match name.as_str() {
-            "true" => Ok(Token::Boolean(true)),
-            "false" => Ok(Token::Boolean(false)),
-            "null" => Ok(Token::Null),
-            _ => Ok(Token::Name(name)),
+    /// Reads `\uXXXX` or `\u{X...}` after the `u`. A leading surrogate must
+    /// be followed by an escaped trailing surrogate, e.g. `😀`.
+    fn read_unicode_escape(&mut self, start: Pos) -> Result<char> {
+        let mut code: u32 = self.read_code_point(start)?;
+
+        if (0xD800..=0xDBFF).contains(&code) && self.lookahead("\\u") {
+            self.bump();
+            self.bump();
+            let trailing: u32 = self.read_code_point(start)?;
+            if (0xDC00..=0xDFFF).contains(&trailing) {
+                code = 0x10000 + ((code - 0xD800) << 10) + (trailing - 0xDC00);
+            }
         }
+
+        char::from_u32(code).ok_or_else(|| {
+            self.error(
+                start,
+                "invalid-escape",
+                format!("Invalid Unicode code point U+{:04X}", code),
+            )
+            .with_help("surrogates must come in pairs, e.g. `\\uD83D\\uDE00`")
+        })
     }
 
-    fn read_number(&mut self, first: char) -> Result<Token> {
-        let mut num = String::new();
-        num.push(first);
+    fn read_code_point(&mut self, start: Pos) -> Result<u32> {
+        let braced: bool = self.peek() == Some(b'{');
+        if braced {
+            self.bump();
+        }
 
+        let digits_start: Pos = self.pos;
         while let Some(c) = self.peek() {
-            if c.is_digit(10) || c == '.' || c == 'e' || c == 'E' || c == '-' || c == '+' {
-                num.push(self.bump().unwrap());
-            } else {
+            let count: usize = self.pos.offset - digits_start.offset;
+            if (braced && c == b'}') || (!braced && count == 4) || !c.is_ascii_hexdigit() {
                 break;
             }
+            self.bump();
+        }
+        let digits: &str = self.slice(digits_start);
+
+        let closed: bool = !braced || self.peek() == Some(b'}');
+        if closed && braced {
+            self.bump();
+        }
+
+        match u32::from_str_radix(digits, 16) {
+            Ok(code) if closed && (braced || digits.len() == 4) => Ok(code),
+            _ => Err(self
+                .error(start, "invalid-escape", "Invalid Unicode escape sequence")
+                .with_help("expected `\\uXXXX` with four hex digits, or `\\u{X...}`")),
+        }
+    }
+
+    fn read_name_or_keyword(&mut self, start: Pos) -> Token<'a> {
+        while matches!(self.peek(), Some(c) if c.is_ascii_alphanumeric() || c == b'_') {
+            self.bump();
+        }
+
+        match self.slice(start) {
+            "true" => Token::Boolean(true),
+            "false" => Token::Boolean(false),
+            "null" => Token::Null,
+            name => Token::Name(name),
+        }
+    }
+
+    /// Reads an IntValue or FloatValue: `-`? (`0` | [1-9][0-9]*), then an
+    /// optional `.digits` fraction and an optional `e[+-]digits` exponent.
+    fn read_number(&mut self, start: Pos) -> Result<Token<'a>> {
+        if self.peek() == Some(b'-') {
+            self.bump();
+            if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                return Err(self.error(
+                    start,
+                    "invalid-number",
+                    "Invalid number: expected a digit after '-'",
+                ));
+            }
+        }
+
+        if self.bump() == Some(b'0') {
+            if self.peek().is_some_and(|c| c.is_ascii_digit()) {
+                self.bump();
+                return Err(self
+                    .error(
+                        start,
+                        "invalid-number",
+                        format!(
+                            "Invalid number '{}': unexpected leading zero",
+                            self.slice(start)
+                        ),
+                    )
+                    .with_help("remove the leading `0`"));
+            }
+        } else {
+            self.read_digits();
+        }
+
+        let mut is_float: bool = false;
+        if self.peek() == Some(b'.') {
+            is_float = true;
+            self.bump();
+            self.read_required_digits(start, "after '.'")?;
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            is_float = true;
+            self.bump();
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.bump();
+            }
+            self.read_required_digits(start, "in the exponent")?;
         }
 
-        if num.contains('.') {
-            Ok(Token::Float(num.parse()?))
+        let num: &'a str = self.slice(start);
+        if let Some(c) = self
+            .peek()
+            .filter(|c| *c == b'.' || c.is_ascii_alphabetic() || *c == b'_')
+        {
+            self.bump();
+            let error = self.error(
+                start,
+                "invalid-number",
+                format!(
+                    "Invalid number '{}': unexpected '{}' after it",
+                    num, c as char
+                ),
+            );
+            return Err(if c == b'.' {
+                error
+            } else {
+                error.with_help("separate the number from the name with a space")
+            });
+        }
+
+        if is_float {
+            num.parse().map(Token::Float).map_err(|e| {
+                self.error(
+                    start,
+                    "invalid-number",
+                    format!("Invalid number '{}': {}", num, e),
+                )
+            })
         } else {
-            Ok(Token::Int(num.parse()?))
+            num.parse().map(Token::Int).map_err(|_| {
+                self.error(
+                    start,
+                    "invalid-number",
+                    format!("Integer '{}' does not fit in 64 bits", num),
+                )
+            })
+        }
+    }
+
+    fn read_digits(&mut self) {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.bump();
+        }
+    }
+
+    fn read_required_digits(&mut self, start: Pos, context: &str) -> Result<()> {
+        if !self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            return Err(self.error(
+                start,
+                "invalid-number",
+                format!(
+                    "Invalid number '{}': expected a digit {}",
+                    self.slice(start),
+                    context
+                ),
+            ));
         }
+        self.read_digits();
+        Ok(())
     }
 
-    fn read_block_string(&mut self) -> Result<Token> {
-        let mut content = String::new();
+    fn read_block_string(&mut self, start: Pos) -> Result<Token<'a>> {
+        let content_start: usize = self.pos.offset;
+        let mut has_escaped_quotes: bool = false;
+
         loop {
-            match self.bump() {
-                Some('"') if self.peek() == Some('"') => {
+            if self.lookahead("\"\"\"") {
+                break;
+            }
+            if self.lookahead("\\\"\"\"") {
+                has_escaped_quotes = true;
+                for _ in 0..4 {
                     self.bump();
-                    if self.peek() == Some('"') {
-                        self.bump();
-                        break;
-                    } else {
-                        content.push('"');
-                    }
                 }
-                Some(c) => content.push(c),
-                None => bail!("Unterminated block string"),
+                continue;
+            }
+            if self.bump().is_none() {
+                return Err(self
+                    .error(start, "unterminated-string", "Unterminated block string")
+                    .with_help("add a closing `\"\"\"`"));
             }
         }
-        Ok(Token::Description(content.trim().to_string()))
+
+        let raw: &'a str = &self.source[content_start..self.pos.offset];
+        for _ in 0..3 {
+            self.bump();
+        }
+
+        Ok(Token::Description(if has_escaped_quotes {
+            Cow::Owned(block_string_value(&raw.replace("\\\"\"\"", "\"\"\"")).into_owned())
+        } else {
+            block_string_value(raw)
+        }))
     }
 }
 
+/// Applies the spec's `BlockStringValue` algorithm to the raw text between
+/// `"""` quotes: removes the indentation common to all lines but the first,
+/// then drops leading and trailing blank lines. Borrows from `raw` when no
+/// indentation has to be removed.
+pub fn block_string_value(raw: &str) -> Cow<'_, str> {
+    let lines: Vec<&str> = raw
+        .split('\n')
+        .flat_map(|line| line.strip_suffix('\r').unwrap_or(line).split('\r'))
+        .collect();
+    let has_crlf: bool = raw.contains('\r');
+
+    let indent = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let common_indent: usize = lines
+        .iter()
+        .skip(1)
+        .filter(|line| indent(line) < line.len())
+        .map(|line| indent(line))
+        .min()
+        .unwrap_or(0);
+
+    let is_blank = |line: &&str| line.trim_matches([' ', '\t']).is_empty();
+    let first: usize = lines.iter().position(|line| !is_blank(line)).unwrap_or(0);
+    let last: usize = lines
+        .iter()
+        .rposition(|line| !is_blank(line))
+        .map_or(0, |i| i + 1);
+    if first >= last {
+        return Cow::Borrowed("");
+    }
+
+    if common_indent == 0 && !has_crlf {
+        // The kept lines are a contiguous slice of `raw`.
+        let begin: usize = lines[first].as_ptr() as usize - raw.as_ptr() as usize;
+        let end: usize =
+            lines[last - 1].as_ptr() as usize - raw.as_ptr() as usize + lines[last - 1].len();
+        return Cow::Borrowed(&raw[begin..end]);
+    }
+
+    let dedented: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line
+            } else {
+                &line[common_indent.min(line.len())..]
+            }
+        })
+        .collect();
+
+    Cow::Owned(dedented[first..last].join("\n"))
+}
+
 impl<'a> Iterator for Lexer<'a> {
-    type Item = Result<Token, String>;
+    type Item = Result<SpannedToken<'a>>;
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.next_token() {
-            Ok(Token::EOF) => None,
-            other => Some(other.map_err(|e| e.to_string())),
+            Ok(SpannedToken {
+                token: Token::EOF, ..
+            }) => None,
+            other => Some(other),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pos(line: usize, column: usize, offset: usize) -> Pos {
+        Pos {
+            line,
+            column,
+            offset,
+        }
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let mut lexer = Lexer::new("type User {\n  id: ID!\n}");
+
+        let expected = vec![
+            (Token::Name("type"), pos(1, 1, 0), pos(1, 5, 4)),
+            (Token::Name("User"), pos(1, 6, 5), pos(1, 10, 9)),
+            (Token::BraceOpen, pos(1, 11, 10), pos(1, 12, 11)),
+            (Token::Name("id"), pos(2, 3, 14), pos(2, 5, 16)),
+            (Token::Colon, pos(2, 5, 16), pos(2, 6, 17)),
+            (Token::Name("ID"), pos(2, 7, 18), pos(2, 9, 20)),
+            (Token::Bang, pos(2, 9, 20), pos(2, 10, 21)),
+            (Token::BraceClose, pos(3, 1, 22), pos(3, 2, 23)),
+        ];
+
+        for (token, start, end) in expected {
+            let spanned = lexer.next_token().unwrap();
+            assert_eq!(spanned.token, token);
+            assert_eq!(spanned.span, Span::new(start, end));
+        }
+        assert_eq!(lexer.next_token().unwrap().token, Token::EOF);
+    }
+
+    #[test]
+    fn test_spans_skip_comments_and_count_bytes() {
+        let mut lexer = Lexer::new("# héllo\n\"ünï\" x");
+
+        let string = lexer.next_token().unwrap();
+        assert_eq!(string.token, Token::String("ünï".into()));
+        assert_eq!(string.span, Span::new(pos(2, 1, 9), pos(2, 6, 16)));
+
+        let name = lexer.next_token().unwrap();
+        assert_eq!(name.span.start, pos(2, 7, 17));
+    }
+
+    #[test]
+    fn test_comments_mode() {
+        let source: &str = "# header\r\ntype A # trailing\n\"#not\" #";
+        let tokens: Vec<Token> = Lexer::new(source)
+            .with_comments()
+            .map(|t| t.unwrap().token)
+            .collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Comment(" header"),
+                Token::Name("type"),
+                Token::Name("A"),
+                Token::Comment(" trailing"),
+                Token::String("#not".into()),
+                Token::Comment(""),
+            ]
+        );
+        assert_eq!(Lexer::new(source).count(), 3);
+    }
+
+    fn lex(source: &str) -> Result<Token<'_>> {
+        Lexer::new(source).next_token().map(|t| t.token)
+    }
+
+    #[test]
+    fn test_string_escapes() {
+        assert_eq!(
+            lex(r#""a \"quoted\" \\ \/ word\n\t""#).unwrap(),
+            Token::String("a \"quoted\" \\ / word\n\t".into())
+        );
+        assert_eq!(
+            lex(r#""\b\f\r""#).unwrap(),
+            Token::String("\u{8}\u{c}\r".into())
+        );
+        assert_eq!(lex(r#""""#).unwrap(), Token::String("".into()));
+    }
+
+    #[test]
+    fn test_tokens_borrow_from_source() {
+        let tokens = lex_all(
+            r#"name "plain" "esc\n" """  indented""" """a
+          b""""#,
+        );
+
+        assert!(matches!(tokens[1], Token::String(Cow::Borrowed("plain"))));
+        assert!(matches!(tokens[2], Token::String(Cow::Owned(_))));
+        assert!(matches!(
+            tokens[3],
+            Token::Description(Cow::Borrowed("  indented"))
+        ));
+        assert_eq!(tokens[4], Token::Description("a\nb".into()));
+        assert!(matches!(tokens[4], Token::Description(Cow::Owned(_))));
+    }
+
+    #[test]
+    fn test_unicode_escapes() {
+        assert_eq!(
+            lex(r#""café \u{1F600} 😀""#).unwrap(),
+            Token::String("café 😀 😀".into())
+        );
+        assert_eq!(lex(r#""\u{0}""#).unwrap(), Token::String("\0".into()));
+        assert_eq!(
+            lex(r#""\uD83D\uDE00 \u00e9""#).unwrap(),
+            Token::String("😀 é".into())
+        );
+    }
+
+    #[test]
+    fn test_invalid_escapes() {
+        let err = lex(r#""a \x b""#).unwrap_err();
+        assert_eq!(err.code, "invalid-escape");
+        assert_eq!(err.message, "Invalid escape sequence '\\x'");
+        assert_eq!(err.span().unwrap().start, pos(1, 4, 3));
+
+        for source in [
+            r#""\u12""#,
+            r#""\u{}""#,
+            r#""\u{110000}""#,
+            r#""\uD83D""#,
+            r#""\u{12""#,
+        ] {
+            assert_eq!(
+                lex(source).unwrap_err().code,
+                "invalid-escape",
+                "{}",
+                source
+            );
+        }
+    }
+
+    #[test]
+    fn test_string_with_raw_newline() {
+        let err = lex("\"first\nsecond\"").unwrap_err();
+        assert_eq!(err.code, "unterminated-string");
+        assert_eq!(err.span().unwrap().end, pos(1, 7, 6));
+    }
+
+    #[test]
+    fn test_block_string_dedent() {
+        let source = "\"\"\"\n    Returns the user.\n\n      Indented line\n    Last\n  \"\"\"";
+        assert_eq!(
+            lex(source).unwrap(),
+            Token::Description("Returns the user.\n\n  Indented line\nLast".into())
+        );
+
+        assert_eq!(
+            lex("\"\"\"  first line keeps its indent\n  second\"\"\"").unwrap(),
+            Token::Description("  first line keeps its indent\nsecond".into())
+        );
+    }
+
+    #[test]
+    fn test_block_string_escaped_quotes() {
+        assert_eq!(
+            lex(r#""""a \""" b \n c""""#).unwrap(),
+            Token::Description(r#"a """ b \n c"#.into())
+        );
+    }
+
+    #[test]
+    fn test_block_string_line_endings() {
+        assert_eq!(
+            block_string_value("\r\n  one\r\n  two\r  three\r\n"),
+            "one\ntwo\nthree"
+        );
+    }
+
+    fn lex_all(source: &str) -> Vec<Token<'_>> {
+        Lexer::new(source).map(|t| t.unwrap().token).collect()
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            lex_all("0 -0 42 -17 1.5 -0.25 1e10 2E-3 6.02e+23"),
+            vec![
+                Token::Int(0),
+                Token::Int(0),
+                Token::Int(42),
+                Token::Int(-17),
+                Token::Float(1.5),
+                Token::Float(-0.25),
+                Token::Float(1e10),
+                Token::Float(2e-3),
+                Token::Float(6.02e23),
+            ]
+        );
+        assert_eq!(lex_all("1-2"), vec![Token::Int(1), Token::Int(-2)]);
+        assert_eq!(
+            lex_all("[1,2]"),
+            vec![
+                Token::BracketOpen,
+                Token::Int(1),
+                Token::Int(2),
+                Token::BracketClose
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_numbers() {
+        let cases = [
+            ("01", "Invalid number '01': unexpected leading zero"),
+            ("-", "Invalid number: expected a digit after '-'"),
+            ("- 1", "Invalid number: expected a digit after '-'"),
+            ("1.", "Invalid number '1.': expected a digit after '.'"),
+            (".5", "Unexpected character: '.'"),
+            (
+                "1e",
+                "Invalid number '1e': expected a digit in the exponent",
+            ),
+            (
+                "1e+",
+                "Invalid number '1e+': expected a digit in the exponent",
+            ),
+            ("1.2.3", "Invalid number '1.2': unexpected '.' after it"),
+            ("123abc", "Invalid number '123': unexpected 'a' after it"),
+            ("0x1F", "Invalid number '0': unexpected 'x' after it"),
+            (
+                "99999999999999999999",
+                "Integer '99999999999999999999' does not fit in 64 bits",
+            ),
+        ];
+
+        for (source, message) in cases {
+            let err = lex(source).unwrap_err();
+            assert_eq!(err.message, message, "{}", source);
+        }
+
+        let err = lex("123abc").unwrap_err();
+        assert_eq!(err.code, "invalid-number");
+        assert_eq!(err.span().unwrap(), Span::new(pos(1, 1, 0), pos(1, 5, 4)));
+    }
+
+    #[test]
+    fn test_incomplete_ellipsis_before_multibyte_character() {
+        let mut lexer = Lexer::new("..é");
+        let err = lexer.next_token().unwrap_err();
+        assert_eq!(err.message, "Unexpected character after '..'");
+        assert_eq!(err.span().unwrap(), Span::new(pos(1, 1, 0), pos(1, 3, 2)));
+        assert_eq!(
+            lexer.next_token().unwrap_err().message,
+            "Unexpected character: 'é'"
+        );
+
+        let mut lexer = Lexer::new(".é");
+        assert_eq!(
+            lexer.next_token().unwrap_err().message,
+            "Unexpected character: '.'"
+        );
+        assert_eq!(
+            lexer.next_token().unwrap_err().message,
+            "Unexpected character: 'é'"
+        );
+        assert!(lexer.next().is_none());
+    }
+
+    #[test]
+    fn test_error_location() {
+        let mut lexer = Lexer::new("type User {\n  id: ID §\n}");
+
+        let err = loop {
+            match lexer.next_token() {
+                Ok(_) => continue,
+                Err(e) => break e,
+            }
+        };
+        assert_eq!(err.code, "unexpected-character");
+        assert_eq!(err.message, "Unexpected character: '§'");
+        assert_eq!(err.span().unwrap().start, pos(2, 10, 21));
+    }
+}
@@ -1,9 +1,13 @@
+pub mod comments;
+pub mod error;
 pub mod lexers;
+pub mod parser;
 pub mod token;
 
 pub mod fields {
-    pub mod parse_field_arguments;
+    pub mod parse_arguments_definition;
     pub mod parse_fields;
+    pub mod parse_input_fields;
 }
 
 pub mod parse;
@@ -0,0 +1,121 @@
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::Argument;
+
+use crate::core::common::{parser::Parser, token::Token};
+
+use super::value::{parse_const_value, parse_value};
+
+/// Parses `(name: value, ...)` passed to a field or to a directive in an
+/// operation, with the parser on the opening paren.
+pub fn parse_arguments(parser: &mut Parser) -> Result<Vec<Argument>> {
+    parse_any_arguments(parser, false)
+}
+
+/// Parses the arguments of a directive applied in the type system, whose
+/// values must be constant.
+pub fn parse_const_arguments(parser: &mut Parser) -> Result<Vec<Argument>> {
+    parse_any_arguments(parser, true)
+}
+
+fn parse_any_arguments(parser: &mut Parser, constant: bool) -> Result<Vec<Argument>> {
+    let open: usize = parser.position();
+    parser.expect(Token::ParenOpen)?;
+
+    let mut arguments: Vec<Argument> = Vec::new();
+    while !parser.at_end() && !parser.at(&Token::ParenClose) {
+        let start: usize = parser.position();
+        let name: String = parser.expect_field_name()?;
+        parser.expect(Token::Colon)?;
+        let value = if constant {
+            parse_const_value(parser)?
+        } else {
+            parse_value(parser)?
+        };
+
+        arguments.push(Argument {
+            name,
+            value,
+            span: parser.span_from(start),
+        });
+        parser.eat(&Token::Comma);
+    }
+
+    if arguments.is_empty() {
+        return Err(parser
+            .unexpected("Expected an argument")
+            .with_secondary(parser.span_at(open), "argument list opened here")
+            .with_help("remove the empty `()`"));
+    }
+    parser.expect(Token::ParenClose)?;
+
+    Ok(arguments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parse::directives::parse_const_directives;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
+    use graphqlgen_schema::ast::Value;
+
+    #[test]
+    fn test_parse_arguments() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::ParenOpen,
+            Token::Name("id"),
+            Token::Colon,
+            Token::Dollar,
+            Token::Name("id"),
+            Token::Name("first"),
+            Token::Colon,
+            Token::Int(10),
+            Token::ParenClose,
+        ]));
+
+        let arguments = parse_arguments(&mut parser).unwrap();
+        assert_eq!(parser.position(), 9);
+        assert_eq!(arguments[0].value, Value::Variable("id".into()));
+        assert_eq!(arguments[1].name, "first");
+        assert_eq!(arguments[1].value, Value::Int(10));
+    }
+
+    #[test]
+    fn test_parse_empty_arguments() {
+        let mut parser = Parser::from_tokens(spanned(vec![Token::ParenOpen, Token::ParenClose]));
+
+        let result = parse_arguments(&mut parser);
+        assert_eq!(result.unwrap_err().message, "Expected an argument");
+    }
+
+    #[test]
+    fn test_parse_const_arguments_with_commas() {
+        let mut parser = Parser::new("(a: 1, b: [true, null])");
+
+        let arguments = parse_const_arguments(&mut parser).unwrap();
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(
+            arguments[1].value,
+            Value::List(vec![Value::Bool(true), Value::Null])
+        );
+        assert!(parser.at_end());
+
+        let mut parser = Parser::new("(id: $id)");
+        let error = parse_const_arguments(&mut parser).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Variables are not allowed in constant values"
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_directive_arguments() {
+        for source in ["@dir() scalar A", "@dir()"] {
+            let mut parser = Parser::new(source);
+
+            let error = parse_const_directives(&mut parser).unwrap_err();
+            assert_eq!(error.message, "Expected an argument");
+            assert_eq!(error.secondary[0].message, "argument list opened here");
+        }
+    }
+}
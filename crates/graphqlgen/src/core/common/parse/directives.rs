@@ -1,51 +1,64 @@
-use anyhow::Result;
-use graphqlgen_schema::ast::{Directive, InputValue, TypeRef};
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{Argument, Deprecation, Directive, Value};
+use graphqlgen_schema::built_ins::DEFAULT_DEPRECATION_REASON;
 
-use crate::core::common::token::Token;
+use crate::core::common::{parser::Parser, token::Token};
 
-use super::{
-    expect::{expect_name, expect_token},
-    value::parse_value,
-};
+use super::arguments::{parse_arguments, parse_const_arguments};
 
-pub fn parse_directives(tokens: &[Token], index: &mut usize) -> Result<Vec<Directive>> {
-    let mut directives: Vec<Directive> = Vec::new();
-
-    while *index < tokens.len() && tokens[*index] == Token::At {
-        *index += 1;
-
-        let directive_name: String = expect_name(tokens, index)?;
-        let mut args: Vec<InputValue> = Vec::new();
-
-        if tokens.get(*index) == Some(&Token::ParenOpen) {
-            *index += 1;
-
-            while *index < tokens.len() && tokens.get(*index) != Some(&Token::ParenClose) {
-                let name: String = expect_name(tokens, index)?;
-                expect_token(tokens, index, Token::Colon)?;
+/// Parses the directives applied in an operation or fragment, whose arguments
+/// may reference variables.
+pub fn parse_directives(parser: &mut Parser) -> Result<Vec<Directive>> {
+    parse_any_directives(parser, false)
+}
 
-                let value = parse_value(tokens, index)?;
+/// Parses the directives applied to a type system definition or a variable
+/// definition, whose arguments must be constant.
+pub fn parse_const_directives(parser: &mut Parser) -> Result<Vec<Directive>> {
+    parse_any_directives(parser, true)
+}
 
-                args.push(InputValue {
-                    name,
-                    value_type: TypeRef::Named("".to_string()),
-                    default_value: Some(value),
-                    description: None,
-                });
+fn parse_any_directives(parser: &mut Parser, constant: bool) -> Result<Vec<Directive>> {
+    let mut directives: Vec<Directive> = Vec::new();
 
-                if tokens.get(*index) == Some(&Token::Comma) {
-                    *index += 1;
-                }
-            }
+    while parser.at(&Token::At) {
+        let start: usize = parser.position();
+        parser.bump();
 
-            expect_token(tokens, index, Token::ParenClose)?;
-        }
+        let directive_name: String = parser.expect_name()?;
+        let args: Vec<Argument> = match (parser.at(&Token::ParenOpen), constant) {
+            (false, _) => Vec::new(),
+            (true, true) => parse_const_arguments(parser)?,
+            (true, false) => parse_arguments(parser)?,
+        };
 
         directives.push(Directive {
             name: directive_name,
             arguments: if args.is_empty() { None } else { Some(args) },
+            span: parser.span_from(start),
         });
     }
 
     Ok(directives)
 }
+
+/// The deprecation declared by a `@deprecated` directive among `directives`,
+/// with the spec's default reason if none is given.
+pub fn deprecation(directives: &[Directive]) -> Option<Deprecation> {
+    let directive: &Directive = directives.iter().find(|d| d.name == "deprecated")?;
+    let reason: Option<&String> = directive
+        .arguments
+        .iter()
+        .flatten()
+        .find(|argument| argument.name == "reason")
+        .and_then(|argument| match &argument.value {
+            Value::String(reason) => Some(reason),
+            _ => None,
+        });
+
+    Some(Deprecation {
+        reason: reason
+            .map_or(DEFAULT_DEPRECATION_REASON, String::as_str)
+            .to_string(),
+    })
+}
@@ -1,128 +1,45 @@
-use anyhow::{anyhow, Result};
-use log::{debug, error};
+use graphqlgen_schema::ast::Span;
 
-use crate::core::common::token::Token;
+use crate::core::common::{error::Diagnostic, token::Token};
 
-pub fn expect_name(tokens: &[Token], index: &mut usize) -> Result<String> {
-    match tokens.get(*index) {
-        Some(Token::Name(name)) => {
-            *index += 1;
-            Ok(name.clone())
-        }
-        other => Err(anyhow!("Expected name, got {:?}", other)),
-    }
+/// Replaces the primary label of `diagnostic` with "expected `what`".
+pub fn expected_here(diagnostic: Diagnostic, what: &str) -> Diagnostic {
+    let span: Span = diagnostic.span().unwrap_or_default();
+    diagnostic.with_primary(span, format!("expected {}", what))
 }
 
-pub fn expect_token(tokens: &[Token], index: &mut usize, expected: Token) -> Result<()> {
-    match tokens.get(*index) {
-        Some(tok) if *tok == expected => {
-            *index += 1;
-            Ok(())
-        }
-        other => {
-            debug!("Tokens: {:?}", tokens);
-            error!("Expected {:?}, got {:?}", expected, other);
-            Err(anyhow!("Expected {:?}, got {:?}", expected, other))
-        }
-    }
+/// Describes the token the parser found, for messages such as "Expected
+/// `:`, found name `User`". `None` is the end of the input.
+pub fn describe_found(token: Option<&Token>) -> String {
+    token.map_or_else(|| "end of input".to_string(), describe)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::common::token::Token;
-
-    #[test]
-    fn test_expect_name_success() {
-        let tokens = vec![Token::Name("test".to_string())];
-        let mut index = 0;
-        let result = expect_name(&tokens, &mut index);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "test");
-        assert_eq!(index, 1);
-    }
-
-    #[test]
-    fn test_expect_name_failure_wrong_token() {
-        let tokens = vec![Token::BraceOpen];
-        let mut index = 0;
-        let result = expect_name(&tokens, &mut index);
-        assert!(result.is_err());
-        assert_eq!(index, 0, "Index should not advance on failure");
-    }
-
-    #[test]
-    fn test_expect_name_failure_out_of_bounds() {
-        let tokens: Vec<Token> = vec![];
-        let mut index = 0;
-        let result = expect_name(&tokens, &mut index);
-        assert!(result.is_err());
-        assert_eq!(index, 0, "Index should remain unchanged");
-    }
-
-    #[test]
-    fn test_expect_token_success() {
-        let tokens = vec![Token::Equals];
-        let mut index = 0;
-        let result = expect_token(&tokens, &mut index, Token::Equals);
-        assert!(result.is_ok());
-        assert_eq!(index, 1);
-    }
-
-    #[test]
-    fn test_expect_token_failure_wrong_token() {
-        let tokens = vec![Token::Pipe];
-        let mut index = 0;
-        let result = expect_token(&tokens, &mut index, Token::Equals);
-        assert!(result.is_err());
-        assert_eq!(index, 0, "Index should not advance on failure");
-    }
-
-    #[test]
-    fn test_expect_token_failure_out_of_bounds() {
-        let tokens: Vec<Token> = vec![];
-        let mut index = 0;
-        let result = expect_token(&tokens, &mut index, Token::Equals);
-        assert!(result.is_err());
-        assert_eq!(index, 0);
-    }
-
-    #[test]
-    fn test_token_list_not_mutated() {
-        let original_tokens = vec![Token::Name("field".into()), Token::Colon];
-        let tokens = original_tokens.clone();
-        let mut index = 0;
-        let _ = expect_name(&tokens, &mut index);
-        assert_eq!(tokens, original_tokens, "Tokens must not be mutated");
-    }
-
-    #[test]
-    fn test_multiple_expect_name_calls() {
-        let tokens = vec![
-            Token::Name("one".into()),
-            Token::Name("two".into()),
-            Token::Name("three".into()),
-        ];
-        let mut index = 0;
-
-        let first = expect_name(&tokens, &mut index).unwrap();
-        assert_eq!(first, "one");
-        assert_eq!(index, 1);
-
-        let second = expect_name(&tokens, &mut index).unwrap();
-        assert_eq!(second, "two");
-        assert_eq!(index, 2);
-
-        let third = expect_name(&tokens, &mut index).unwrap();
-        assert_eq!(third, "three");
-        assert_eq!(index, 3);
-    }
-
-    #[test]
-    fn test_expect_token_does_not_consume_non_matching_token() {
-        let tokens = vec![Token::Colon];
-        let mut index = 0;
-        let _ = expect_token(&tokens, &mut index, Token::Equals);
-        assert_eq!(index, 0, "Index should not move if token does not match");
+/// A short, user facing name for a token, used in diagnostic labels.
+pub fn describe(token: &Token) -> String {
+    match token {
+        Token::Name(name) => format!("name `{}`", name),
+        Token::Int(i) => format!("integer `{}`", i),
+        Token::Float(f) => format!("float `{}`", f),
+        Token::String(_) => "string".to_string(),
+        Token::Description(_) => "block string".to_string(),
+        Token::Boolean(b) => format!("`{}`", b),
+        Token::Null => "`null`".to_string(),
+        Token::BraceOpen => "`{`".to_string(),
+        Token::BraceClose => "`}`".to_string(),
+        Token::ParenOpen => "`(`".to_string(),
+        Token::ParenClose => "`)`".to_string(),
+        Token::BracketOpen => "`[`".to_string(),
+        Token::BracketClose => "`]`".to_string(),
+        Token::Colon => "`:`".to_string(),
+        Token::Equals => "`=`".to_string(),
+        Token::At => "`@`".to_string(),
+        Token::Ellipsis => "`...`".to_string(),
+        Token::Dollar => "`$`".to_string(),
+        Token::Bang => "`!`".to_string(),
+        Token::Pipe => "`|`".to_string(),
+        Token::Ampersand => "`&`".to_string(),
+        Token::Comma => "`,`".to_string(),
+        Token::Comment(_) => "comment".to_string(),
+        Token::EOF => "end of input".to_string(),
     }
 }
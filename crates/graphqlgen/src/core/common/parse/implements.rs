@@ -0,0 +1,64 @@
+use crate::core::common::{error::Result, parser::Parser, token::Token};
+
+/// Parses an optional `implements A & B` clause. A leading `&` is allowed
+/// before the first interface.
+pub fn parse_implements(parser: &mut Parser) -> Result<Vec<String>> {
+    let mut interfaces: Vec<String> = Vec::new();
+
+    if !parser.eat_keyword("implements") {
+        return Ok(interfaces);
+    }
+
+    parser.eat(&Token::Ampersand);
+
+    interfaces.push(parser.expect_name()?);
+    while parser.eat(&Token::Ampersand) {
+        interfaces.push(parser.expect_name()?);
+    }
+
+    Ok(interfaces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
+
+    #[test]
+    fn test_parse_implements() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("implements"),
+            Token::Ampersand,
+            Token::Name("Node"),
+            Token::Ampersand,
+            Token::Name("Entity"),
+            Token::BraceOpen,
+        ]));
+
+        let interfaces = parse_implements(&mut parser).unwrap();
+        assert_eq!(interfaces, vec!["Node", "Entity"]);
+        assert_eq!(parser.position(), 5);
+    }
+
+    #[test]
+    fn test_parse_without_implements() {
+        let mut parser = Parser::from_tokens(spanned(vec![Token::At, Token::Name("key")]));
+
+        assert!(parse_implements(&mut parser).unwrap().is_empty());
+        assert_eq!(parser.position(), 0);
+    }
+
+    #[test]
+    fn test_parse_implements_trailing_ampersand() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("implements"),
+            Token::Name("Node"),
+            Token::Ampersand,
+            Token::BraceOpen,
+        ]));
+
+        let result = parse_implements(&mut parser);
+        assert_eq!(result.unwrap_err().message, "Expected a name, found `{`");
+    }
+}
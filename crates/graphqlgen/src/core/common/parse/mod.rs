@@ -1,5 +1,7 @@
-pub mod description;
+pub mod arguments;
 pub mod directives;
 pub mod expect;
+pub mod implements;
+pub mod recover;
 pub mod type_ref;
 pub mod value;
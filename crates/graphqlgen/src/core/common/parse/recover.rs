@@ -0,0 +1,143 @@
+use graphqlgen_schema::ast::OperationType;
+
+use crate::core::common::{parser::Parser, token::Token};
+
+/// Keywords that start a named type system definition at the top level.
+pub const DEFINITION_KEYWORDS: &[&str] = &["type", "input", "enum", "interface", "union", "scalar"];
+
+/// Returns `true` if a definition starts `offset` tokens past the current
+/// one: a definition keyword followed by the definition's name (or, for
+/// `schema` and `directive`, by `{` or `@`), or `extend` followed by such a
+/// keyword. The lookahead keeps fields and values that happen to be called
+/// `type` or `schema` from being mistaken for one.
+pub fn starts_definition(parser: &Parser, offset: usize) -> bool {
+    let previous: Option<&Token> = match offset {
+        0 => parser.previous(),
+        _ => parser.nth(offset - 1),
+    };
+    let next: Option<&Token> = parser.nth(offset + 1);
+
+    // The keyword of an extension belongs to the `extend` before it.
+    if matches!(previous, Some(Token::Name(name)) if *name == "extend") {
+        return false;
+    }
+
+    match parser.nth(offset) {
+        Some(Token::Name(name)) if *name == "extend" => matches!(
+            next,
+            Some(Token::Name(keyword))
+                if *keyword == "schema" || DEFINITION_KEYWORDS.contains(keyword)
+        ),
+        Some(Token::Name(name)) if *name == "schema" => {
+            matches!(next, Some(Token::BraceOpen | Token::At))
+        }
+        Some(Token::Name(name)) if *name == "directive" => matches!(next, Some(Token::At)),
+        Some(Token::Name(name)) if DEFINITION_KEYWORDS.contains(name) => {
+            matches!(next, Some(Token::Name(_)))
+        }
+        _ => false,
+    }
+}
+
+/// Counts the braces left open by the tokens from `start` up to the current
+/// one, leaving the parser at `start`.
+fn open_braces(parser: &mut Parser, start: usize) -> usize {
+    let error_index: usize = parser.position();
+    parser.reset(start);
+
+    let mut depth: usize = 0;
+    while parser.position() < error_index {
+        match parser.peek() {
+            Some(Token::BraceOpen) => depth += 1,
+            Some(Token::BraceClose) => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        parser.bump();
+    }
+    parser.reset(start);
+    depth
+}
+
+/// Moves the parser to where parsing can resume after the definition that
+/// started at `start` failed with an error at the current token.
+///
+/// Skips to just past the `}` that closes the broken definition, or to the
+/// next definition (or its description) if that comes first, so a missing
+/// closing brace does not swallow the rest of the document. Always makes
+/// progress.
+pub fn recover(parser: &mut Parser, start: usize) {
+    let error_index: usize = parser.position().max(start);
+    // Braces already balanced before the error (e.g. in directive arguments)
+    // do not close the definition.
+    let mut depth: usize = open_braces(parser, start);
+
+    let described: bool = matches!(
+        parser.peek(),
+        Some(Token::Description(_) | Token::String(_))
+    );
+    parser.bump();
+    // The keyword after the broken definition's own description would
+    // otherwise be taken for the next definition, unless the error is the
+    // description itself.
+    if described && parser.position() < error_index && starts_definition(parser, 0) {
+        parser.bump();
+    }
+    while let Some(token) = parser.peek() {
+        let past_error: bool = parser.position() >= error_index;
+        match token {
+            Token::Description(_) | Token::String(_) if starts_definition(parser, 1) => return,
+            Token::Name(_) if starts_definition(parser, 0) => return,
+            Token::BraceOpen if past_error => depth += 1,
+            Token::BraceClose if past_error => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    parser.bump();
+                    return;
+                }
+            }
+            _ => {}
+        }
+        parser.bump();
+    }
+}
+
+/// Returns `true` if an operation or fragment starts at the current token.
+pub fn starts_executable_definition(parser: &Parser) -> bool {
+    match parser.peek() {
+        Some(Token::BraceOpen) => true,
+        Some(Token::Name(name)) if *name == "fragment" => {
+            matches!(parser.nth(1), Some(Token::Name(_)))
+        }
+        Some(Token::Name(name)) => OperationType::from_keyword(name).is_some(),
+        _ => false,
+    }
+}
+
+/// Like [`recover`], for executable documents. Operation keywords are also
+/// valid field names, so parsing only resumes outside of selection sets, and
+/// never at a `{` shorthand query, which is as likely to be the body of the
+/// broken definition.
+pub fn recover_executable(parser: &mut Parser, start: usize) {
+    let error_index: usize = parser.position().max(start);
+    let mut depth: usize = open_braces(parser, start);
+
+    parser.reset(error_index);
+    if parser.position() == start {
+        parser.bump();
+    }
+    while let Some(token) = parser.peek() {
+        match token {
+            Token::BraceClose => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    parser.bump();
+                    return;
+                }
+            }
+            Token::Name(_) if depth == 0 && starts_executable_definition(parser) => return,
+            Token::BraceOpen => depth += 1,
+            _ => {}
+        }
+        parser.bump();
+    }
+}
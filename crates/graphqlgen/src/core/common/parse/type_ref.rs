@@ -1,60 +1,67 @@
-use anyhow::{anyhow, Result};
+use crate::core::common::error::Result;
 use graphqlgen_schema::ast::TypeRef;
 
-use crate::core::common::token::Token;
+use crate::core::common::{parse::expect::describe_found, parser::Parser, token::Token};
 
-pub fn parse_type_ref(tokens: &[Token]) -> Result<(TypeRef, usize)> {
-    let mut consumed = 0;
+/// Parses a type reference: a name or `[Type]`, optionally followed by `!`.
+pub fn parse_type_ref(parser: &mut Parser) -> Result<TypeRef> {
+    let start: usize = parser.position();
 
-    let base_type = match tokens.get(consumed) {
+    let base_type = match parser.peek() {
         Some(Token::Name(name)) => {
-            consumed += 1;
-            TypeRef::Named(name.clone())
+            let name: String = name.to_string();
+            parser.bump();
+            TypeRef::Named(name)
         }
         Some(Token::BracketOpen) => {
-            consumed += 1;
-            let (inner, inner_consumed) = parse_type_ref(&tokens[consumed..])?;
-            consumed += inner_consumed;
+            parser.bump();
+            let inner: TypeRef = parse_type_ref(parser)?;
 
-            if tokens.get(consumed) != Some(&Token::BracketClose) {
-                return Err(anyhow!("Expected closing bracket for list type"));
+            if !parser.eat(&Token::BracketClose) {
+                return Err(parser
+                    .unexpected("Expected closing bracket for list type")
+                    .with_secondary(parser.span_at(start), "list type opened here"));
             }
-            consumed += 1;
             TypeRef::List(Box::new(inner))
         }
-        other => return Err(anyhow!("Unexpected token in type reference: {:?}", other)),
+        other => {
+            return Err(parser.unexpected(format!(
+                "Unexpected {} in type reference",
+                describe_found(other)
+            )))
+        }
     };
 
-    if tokens.get(consumed) == Some(&Token::Bang) {
-        consumed += 1;
-        Ok((TypeRef::NonNull(Box::new(base_type)), consumed))
+    if parser.eat(&Token::Bang) {
+        Ok(TypeRef::NonNull(Box::new(base_type)))
     } else {
-        Ok((base_type, consumed))
+        Ok(base_type)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::common::token::Token;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::{spanned, Token};
 
     #[test]
     fn test_named_type() {
-        let tokens = vec![Token::Name("String".into())];
-        let result = parse_type_ref(&tokens);
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Name("String")]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_ok());
-        let (type_ref, consumed) = result.unwrap();
-        assert_eq!(consumed, 1);
+        let type_ref = result.unwrap();
+        assert_eq!(parser.position(), 1);
         assert!(matches!(type_ref, TypeRef::Named(name) if name == "String"));
     }
 
     #[test]
     fn test_non_null_named_type() {
-        let tokens = vec![Token::Name("ID".into()), Token::Bang];
-        let result = parse_type_ref(&tokens);
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Name("ID"), Token::Bang]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_ok());
-        let (type_ref, consumed) = result.unwrap();
-        assert_eq!(consumed, 2);
+        let type_ref = result.unwrap();
+        assert_eq!(parser.position(), 2);
         match type_ref {
             TypeRef::NonNull(inner) => match *inner {
                 TypeRef::Named(name) => assert_eq!(name, "ID"),
@@ -66,15 +73,15 @@ mod tests {
 
     #[test]
     fn test_list_of_named() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BracketOpen,
-            Token::Name("String".into()),
+            Token::Name("String"),
             Token::BracketClose,
-        ];
-        let result = parse_type_ref(&tokens);
+        ]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_ok());
-        let (type_ref, consumed) = result.unwrap();
-        assert_eq!(consumed, 3);
+        let type_ref = result.unwrap();
+        assert_eq!(parser.position(), 3);
         match type_ref {
             TypeRef::List(inner) => match *inner {
                 TypeRef::Named(name) => assert_eq!(name, "String"),
@@ -86,16 +93,16 @@ mod tests {
 
     #[test]
     fn test_list_of_non_null_named() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BracketOpen,
-            Token::Name("String".into()),
+            Token::Name("String"),
             Token::Bang,
             Token::BracketClose,
-        ];
-        let result = parse_type_ref(&tokens);
+        ]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_ok());
-        let (type_ref, consumed) = result.unwrap();
-        assert_eq!(consumed, 4);
+        let type_ref = result.unwrap();
+        assert_eq!(parser.position(), 4);
         match type_ref {
             TypeRef::List(inner) => match *inner {
                 TypeRef::NonNull(inner2) => match *inner2 {
@@ -110,16 +117,16 @@ mod tests {
 
     #[test]
     fn test_non_null_list() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BracketOpen,
-            Token::Name("Int".into()),
+            Token::Name("Int"),
             Token::BracketClose,
             Token::Bang,
-        ];
-        let result = parse_type_ref(&tokens);
+        ]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_ok());
-        let (type_ref, consumed) = result.unwrap();
-        assert_eq!(consumed, 4);
+        let type_ref = result.unwrap();
+        assert_eq!(parser.position(), 4);
         match type_ref {
             TypeRef::NonNull(inner) => match *inner {
                 TypeRef::List(inner2) => match *inner2 {
@@ -134,18 +141,18 @@ mod tests {
 
     #[test]
     fn test_nested_list_non_null() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BracketOpen,
             Token::BracketOpen,
-            Token::Name("String".into()),
+            Token::Name("String"),
             Token::BracketClose,
             Token::BracketClose,
             Token::Bang,
-        ];
-        let result = parse_type_ref(&tokens);
+        ]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_ok());
-        let (type_ref, consumed) = result.unwrap();
-        assert_eq!(consumed, 6);
+        let type_ref = result.unwrap();
+        assert_eq!(parser.position(), 6);
 
         match type_ref {
             TypeRef::NonNull(inner) => match *inner {
@@ -164,29 +171,41 @@ mod tests {
 
     #[test]
     fn test_invalid_missing_closing_bracket() {
-        let tokens = vec![Token::BracketOpen, Token::Name("String".into())];
-        let result = parse_type_ref(&tokens);
+        let mut parser =
+            Parser::from_tokens(spanned(vec![Token::BracketOpen, Token::Name("String")]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_missing_closing_bracket_points_at_opening_bracket() {
+        let mut parser = Parser::new("[String id");
+        let err = parse_type_ref(&mut parser).unwrap_err();
+
+        assert_eq!(err.code, "unexpected-token");
+        assert_eq!(err.span().unwrap().start.column, 9);
+        assert_eq!(err.secondary.len(), 1);
+        assert_eq!(err.secondary[0].span.start.column, 1);
+    }
+
     #[test]
     fn test_invalid_unexpected_token() {
-        let tokens = vec![Token::Colon];
-        let result = parse_type_ref(&tokens);
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Colon]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_empty_input() {
-        let tokens: Vec<Token> = vec![];
-        let result = parse_type_ref(&tokens);
+        let mut parser = Parser::from_tokens(spanned(vec![]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_bang_without_base_type() {
-        let tokens = vec![Token::Bang];
-        let result = parse_type_ref(&tokens);
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Bang]));
+        let result = parse_type_ref(&mut parser);
         assert!(result.is_err());
     }
 }
@@ -1,129 +1,141 @@
-use anyhow::{anyhow, Result};
+use crate::core::common::error::Result;
 use graphqlgen_schema::ast::Value;
 
-use crate::core::common::token::Token;
+use crate::core::common::{parse::expect::describe_found, parser::Parser, token::Token};
 
-use super::expect::{expect_name, expect_token};
+/// Parses a value, which may reference variables, as in the arguments of a
+/// selected field.
+pub fn parse_value(parser: &mut Parser) -> Result<Value> {
+    parse_any_value(parser, false)
+}
 
-pub fn parse_value(tokens: &[Token], index: &mut usize) -> Result<Value> {
-    match tokens.get(*index) {
-        Some(Token::String(s)) => {
-            *index += 1;
-            Ok(Value::String(s.clone()))
-        }
-        Some(Token::Int(i)) => {
-            *index += 1;
-            Ok(Value::Int(*i)) // assuming Value::Int(i32) exists in your AST
-        }
-        Some(Token::Name(n)) if n == "true" || n == "false" => {
-            let b = n == "true";
-            *index += 1;
-            Ok(Value::Bool(b))
+/// Parses a constant value, as in default values and the directives of type
+/// system definitions, where variables are an error.
+pub fn parse_const_value(parser: &mut Parser) -> Result<Value> {
+    parse_any_value(parser, true)
+}
+
+fn parse_any_value(parser: &mut Parser, constant: bool) -> Result<Value> {
+    let value: Value = match parser.peek() {
+        Some(Token::String(s) | Token::Description(s)) => Value::String(s.to_string()),
+        Some(Token::Int(i)) => Value::Int(*i),
+        Some(Token::Float(f)) => Value::Float(*f),
+        Some(Token::Boolean(b)) => Value::Bool(*b),
+        Some(Token::Null) => Value::Null,
+        Some(Token::Name(n)) => Value::Enum(n.to_string()),
+        Some(Token::Dollar) if constant => {
+            return Err(parser
+                .unexpected("Variables are not allowed in constant values")
+                .with_help("use a literal value; variables can only be used in operations"));
         }
-        Some(Token::Name(n)) => {
-            *index += 1;
-            Ok(Value::Enum(n.clone()))
+        Some(Token::Dollar) => {
+            parser.bump();
+            return Ok(Value::Variable(parser.expect_name()?));
         }
         Some(Token::BraceOpen) => {
-            *index += 1;
+            parser.bump();
             let mut fields = Vec::new();
-            while *index < tokens.len() && tokens.get(*index) != Some(&Token::BraceClose) {
-                let key = expect_name(tokens, index)?;
-                expect_token(tokens, index, Token::Colon)?;
-                let val = parse_value(tokens, index)?;
+            while !parser.at_end() && !parser.at(&Token::BraceClose) {
+                let key = parser.expect_field_name()?;
+                parser.expect(Token::Colon)?;
+                let val = parse_any_value(parser, constant)?;
                 fields.push((key, val));
-
-                if tokens.get(*index) == Some(&Token::Comma) {
-                    *index += 1;
-                }
+                parser.eat(&Token::Comma);
             }
-            expect_token(tokens, index, Token::BraceClose)?;
-            Ok(Value::Object(fields))
+            parser.expect(Token::BraceClose)?;
+            return Ok(Value::Object(fields));
         }
         Some(Token::BracketOpen) => {
-            *index += 1;
+            parser.bump();
             let mut items = Vec::new();
-            while *index < tokens.len() && tokens.get(*index) != Some(&Token::BracketClose) {
-                let val = parse_value(tokens, index)?;
+            while !parser.at_end() && !parser.at(&Token::BracketClose) {
+                let val = parse_any_value(parser, constant)?;
                 items.push(val);
-
-                if tokens.get(*index) == Some(&Token::Comma) {
-                    *index += 1;
-                }
+                parser.eat(&Token::Comma);
             }
-            expect_token(tokens, index, Token::BracketClose)?;
-            Ok(Value::List(items))
+            parser.expect(Token::BracketClose)?;
+            return Ok(Value::List(items));
         }
-        other => Err(anyhow!("Unexpected token as value: {:?}", other)),
-    }
+        other => {
+            return Err(
+                parser.unexpected(format!("Expected a value, found {}", describe_found(other)))
+            )
+        }
+    };
+
+    parser.bump();
+    Ok(value)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::common::token::Token;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::{spanned, Token};
 
     #[test]
     fn test_parse_string() {
-        let tokens = vec![Token::String("hello".into())];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index).unwrap();
+        let mut parser = Parser::from_tokens(spanned(vec![Token::String("hello".into())]));
+        let result = parse_value(&mut parser).unwrap();
         assert_eq!(result, Value::String("hello".into()));
-        assert_eq!(index, 1);
+        assert_eq!(parser.position(), 1);
     }
 
     #[test]
     fn test_parse_int() {
-        let tokens = vec![Token::Int(42)];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index).unwrap();
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Int(42)]));
+        let result = parse_value(&mut parser).unwrap();
         assert_eq!(result, Value::Int(42));
-        assert_eq!(index, 1);
+        assert_eq!(parser.position(), 1);
     }
 
     #[test]
     fn test_parse_bool_true() {
-        let tokens = vec![Token::Name("true".into())];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index).unwrap();
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Boolean(true)]));
+        let result = parse_value(&mut parser).unwrap();
         assert_eq!(result, Value::Bool(true));
-        assert_eq!(index, 1);
+        assert_eq!(parser.position(), 1);
     }
 
     #[test]
     fn test_parse_bool_false() {
-        let tokens = vec![Token::Name("false".into())];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index).unwrap();
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Boolean(false)]));
+        let result = parse_value(&mut parser).unwrap();
         assert_eq!(result, Value::Bool(false));
-        assert_eq!(index, 1);
+        assert_eq!(parser.position(), 1);
     }
 
     #[test]
     fn test_parse_enum() {
-        let tokens = vec![Token::Name("SOME_ENUM".into())];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index).unwrap();
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Name("SOME_ENUM")]));
+        let result = parse_value(&mut parser).unwrap();
         assert_eq!(result, Value::Enum("SOME_ENUM".into()));
-        assert_eq!(index, 1);
+        assert_eq!(parser.position(), 1);
+    }
+
+    #[test]
+    fn test_parse_variable() {
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Dollar, Token::Name("id")]));
+        let result = parse_value(&mut parser).unwrap();
+        assert_eq!(result, Value::Variable("id".into()));
+        assert_eq!(parser.position(), 2);
     }
 
     #[test]
     fn test_parse_object() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BraceOpen,
-            Token::Name("foo".into()),
+            Token::Name("foo"),
             Token::Colon,
             Token::Int(7),
             Token::Comma,
-            Token::Name("bar".into()),
+            Token::Name("bar"),
             Token::Colon,
             Token::String("baz".into()),
             Token::BraceClose,
-        ];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index).unwrap();
-        assert_eq!(index, 9);
+        ]));
+        let result = parse_value(&mut parser).unwrap();
+        assert_eq!(parser.position(), 9);
         assert_eq!(
             result,
             Value::Object(vec![
@@ -135,18 +147,17 @@ mod tests {
 
     #[test]
     fn test_parse_list() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BracketOpen,
             Token::String("one".into()),
             Token::Comma,
             Token::Int(2),
             Token::Comma,
-            Token::Name("false".into()),
+            Token::Boolean(false),
             Token::BracketClose,
-        ];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index).unwrap();
-        assert_eq!(index, 7);
+        ]));
+        let result = parse_value(&mut parser).unwrap();
+        assert_eq!(parser.position(), 7);
         assert_eq!(
             result,
             Value::List(vec![
@@ -159,22 +170,21 @@ mod tests {
 
     #[test]
     fn test_parse_nested() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BraceOpen,
-            Token::Name("nested".into()),
+            Token::Name("nested"),
             Token::Colon,
             Token::BracketOpen,
             Token::BraceOpen,
-            Token::Name("ok".into()),
+            Token::Name("ok"),
             Token::Colon,
-            Token::Name("true".into()),
+            Token::Boolean(true),
             Token::BraceClose,
             Token::BracketClose,
             Token::BraceClose,
-        ];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index).unwrap();
-        assert_eq!(index, 11);
+        ]));
+        let result = parse_value(&mut parser).unwrap();
+        assert_eq!(parser.position(), 11);
         assert_eq!(
             result,
             Value::Object(vec![(
@@ -184,60 +194,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_lexed_values() {
+        let mut parser = Parser::new(
+            r#"[1.5, -2e3, true, false, null, "s", """block
+  string""", $id, { a: [null] }]"#,
+        );
+        let result = parse_value(&mut parser).unwrap();
+        assert!(parser.at_end());
+        assert_eq!(
+            result,
+            Value::List(vec![
+                Value::Float(1.5),
+                Value::Float(-2000.0),
+                Value::Bool(true),
+                Value::Bool(false),
+                Value::Null,
+                Value::String("s".into()),
+                Value::String("block\nstring".into()),
+                Value::Variable("id".into()),
+                Value::Object(vec![("a".into(), Value::List(vec![Value::Null]))]),
+            ])
+        );
+        assert!(!result.is_const());
+    }
+
+    #[test]
+    fn test_parse_const_value() {
+        let mut parser = Parser::new(r#"{ limit: 10, tags: ["a", null] }"#);
+        let result = parse_const_value(&mut parser).unwrap();
+        assert!(result.is_const());
+
+        let mut parser = Parser::new("{ ids: [1, $id] }");
+        let error = parse_const_value(&mut parser).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Variables are not allowed in constant values"
+        );
+        assert_eq!(error.span().unwrap().start.column, 12);
+    }
+
     // --- INVALID CASES ---
 
     #[test]
     fn test_unexpected_token_error() {
-        let tokens = vec![Token::Colon];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index);
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Colon]));
+        let result = parse_value(&mut parser);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_object_missing_colon() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BraceOpen,
-            Token::Name("foo".into()),
+            Token::Name("foo"),
             Token::String("oops".into()), // Missing colon
             Token::BraceClose,
-        ];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index);
+        ]));
+        let result = parse_value(&mut parser);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_object_missing_brace_close() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BraceOpen,
-            Token::Name("x".into()),
+            Token::Name("x"),
             Token::Colon,
             Token::Int(1),
-        ]; // No closing brace
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index);
+        ])); // No closing brace
+        let result = parse_value(&mut parser);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_list_missing_bracket_close() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::BracketOpen,
             Token::Int(1),
             Token::Comma,
             Token::Int(2),
-        ];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index);
+        ]));
+        let result = parse_value(&mut parser);
         assert!(result.is_err());
     }
 
     #[test]
     fn test_empty_token_stream() {
-        let tokens = vec![];
-        let mut index = 0;
-        let result = parse_value(&tokens, &mut index);
+        let mut parser = Parser::from_tokens(spanned(vec![]));
+        let result = parse_value(&mut parser);
         assert!(result.is_err());
     }
 }
@@ -0,0 +1,514 @@
+use std::collections::VecDeque;
+
+use graphqlgen_schema::ast::{Comment, Directive, Pos, Span, TypeRef, Value};
+
+use super::comments::Trivia;
+use super::error::{Diagnostic, Result};
+use super::lexers::Lexer;
+use super::parse::expect::{describe, describe_found, expected_here};
+use super::parse::{directives, type_ref, value};
+use super::token::{SpannedToken, Token};
+
+/// How many tokens past the current one [`Parser::nth`] can look at.
+pub const LOOKAHEAD: usize = 2;
+
+/// A cursor over a token stream, shared by every parse function.
+///
+/// Tokens are pulled from the lexer as the parser advances, keeping
+/// [`LOOKAHEAD`] tokens buffered past the current one. Consumed tokens are kept
+/// until [`Parser::release`], so spans can be computed for finished nodes and
+/// error recovery can rewind. The document parsers release them between
+/// definitions, so only the tokens of one definition are held at a time.
+/// Lexer errors are collected rather than returned; see
+/// [`Parser::take_lex_errors`]. So are comments, which parse functions never
+/// see; see [`Parser::with_comments`].
+///
+/// ```
+/// use graphqlgen::core::common::parser::{parse_str, Parser};
+/// use graphqlgen_schema::ast::TypeRef;
+///
+/// let type_ref = parse_str("[String!]!", Parser::parse_type_ref).unwrap();
+/// assert!(matches!(type_ref, TypeRef::NonNull(_)));
+/// ```
+pub struct Parser<'a> {
+    lexer: Option<Lexer<'a>>,
+    tokens: VecDeque<SpannedToken<'a>>,
+    /// The position of the first buffered token; those before it were
+    /// released.
+    first: usize,
+    index: usize,
+    lex_errors: Vec<Diagnostic>,
+    comments: Vec<Trivia>,
+    /// Whether the last token or comment read was a comment.
+    after_comment: bool,
+}
+
+impl<'a> Parser<'a> {
+    /// A parser that lexes `source` on demand.
+    pub fn new(source: &'a str) -> Self {
+        Self::from_lexer(Lexer::new(source))
+    }
+
+    /// A parser that keeps the `#` comments in `source`, for
+    /// [`attach_comments`] to attach to the parsed nodes.
+    ///
+    /// [`attach_comments`]: super::comments::attach_comments
+    pub fn with_comments(source: &'a str) -> Self {
+        Self::from_lexer(Lexer::new(source).with_comments())
+    }
+
+    fn from_lexer(lexer: Lexer<'a>) -> Self {
+        let mut parser = Self {
+            lexer: Some(lexer),
+            tokens: VecDeque::new(),
+            first: 0,
+            index: 0,
+            lex_errors: Vec::new(),
+            comments: Vec::new(),
+            after_comment: false,
+        };
+        parser.fill();
+        parser
+    }
+
+    /// A parser over tokens that were already lexed, or built by hand.
+    pub fn from_tokens(tokens: Vec<SpannedToken<'a>>) -> Self {
+        let mut parser = Self {
+            lexer: None,
+            tokens: VecDeque::with_capacity(tokens.len()),
+            first: 0,
+            index: 0,
+            lex_errors: Vec::new(),
+            comments: Vec::new(),
+            after_comment: false,
+        };
+        tokens.into_iter().for_each(|token| parser.push(token));
+        parser
+    }
+
+    /// Buffers `token`, or sets it aside if it is a comment.
+    fn push(&mut self, token: SpannedToken<'a>) {
+        if self.after_comment {
+            if let Some(trivia) = self.comments.last_mut() {
+                trivia.comment.blank_line_after =
+                    token.span.start.line > trivia.comment.span.end.line + 1;
+            }
+        }
+        self.after_comment = matches!(token.token, Token::Comment(_));
+
+        let Token::Comment(text) = token.token else {
+            self.tokens.push_back(token);
+            return;
+        };
+        let previous: Option<Pos> = self.tokens.back().map(|t| t.span.end);
+        self.comments.push(Trivia {
+            comment: Comment {
+                text: text.to_string(),
+                blank_line_after: false,
+                span: token.span,
+            },
+            own_line: previous.is_none_or(|end| end.line < token.span.start.line),
+            after: previous.map_or(0, |end| end.offset),
+        });
+    }
+
+    /// Lexes until [`LOOKAHEAD`] tokens past the current one are buffered, or
+    /// the input runs out.
+    fn fill(&mut self) {
+        while self.end() <= self.index + LOOKAHEAD {
+            let Some(lexer) = &mut self.lexer else {
+                return;
+            };
+            match lexer.next() {
+                Some(Ok(token)) => self.push(token),
+                Some(Err(diagnostic)) => self.lex_errors.push(diagnostic),
+                None => self.lexer = None,
+            }
+        }
+    }
+
+    /// The position just past the last buffered token.
+    fn end(&self) -> usize {
+        self.first + self.tokens.len()
+    }
+
+    /// The buffered token at `position`.
+    fn token(&self, position: usize) -> Option<&SpannedToken<'a>> {
+        position
+            .checked_sub(self.first)
+            .and_then(|i| self.tokens.get(i))
+    }
+
+    /// The current token, or `None` at the end of the input.
+    pub fn peek(&self) -> Option<&Token<'a>> {
+        self.nth(0)
+    }
+
+    /// The token `n` places past the current one. `n` must not exceed
+    /// [`LOOKAHEAD`].
+    pub fn nth(&self, n: usize) -> Option<&Token<'a>> {
+        debug_assert!(n <= LOOKAHEAD, "looked {} tokens ahead", n);
+        self.token(self.index + n).map(|t| &t.token)
+    }
+
+    /// The token before the current one.
+    pub fn previous(&self) -> Option<&Token<'a>> {
+        self.index
+            .checked_sub(1)
+            .and_then(|i| self.token(i))
+            .map(|t| &t.token)
+    }
+
+    /// Returns `true` if the current token is `token`.
+    pub fn at(&self, token: &Token) -> bool {
+        self.peek() == Some(token)
+    }
+
+    /// Returns `true` if the current token is the name `keyword`. Keywords
+    /// are not reserved in GraphQL, so they are lexed as names.
+    pub fn at_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Name(name)) if *name == keyword)
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.index >= self.end()
+    }
+
+    /// Moves past the current token.
+    pub fn bump(&mut self) {
+        if self.index < self.end() {
+            self.index += 1;
+            self.fill();
+        }
+    }
+
+    /// Moves past the current token if it is `token`.
+    pub fn eat(&mut self, token: &Token) -> bool {
+        let found: bool = self.at(token);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    /// Moves past the current token if it is the name `keyword`.
+    pub fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found: bool = self.at_keyword(keyword);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    pub fn expect(&mut self, expected: Token) -> Result<()> {
+        if self.eat(&expected) {
+            return Ok(());
+        }
+        Err(expected_here(
+            self.unexpected(format!(
+                "Expected {}, found {}",
+                describe(&expected),
+                describe_found(self.peek())
+            )),
+            &describe(&expected),
+        ))
+    }
+
+    pub fn expect_name(&mut self) -> Result<String> {
+        match self.peek() {
+            Some(Token::Name(name)) => {
+                let name: String = name.to_string();
+                self.bump();
+                Ok(name)
+            }
+            other => Err(expected_here(
+                self.unexpected(format!("Expected a name, found {}", describe_found(other))),
+                "a name",
+            )),
+        }
+    }
+
+    /// Expects the name of a field, argument or input field. `true`, `false`
+    /// and `null` are lexed as values but are valid names here, unlike for
+    /// enum values.
+    pub fn expect_field_name(&mut self) -> Result<String> {
+        let name: String = match self.peek() {
+            Some(Token::Boolean(b)) => b.to_string(),
+            Some(Token::Null) => "null".to_string(),
+            _ => return self.expect_name(),
+        };
+        self.bump();
+        Ok(name)
+    }
+
+    /// Expects the name `keyword`, e.g. the `on` of a fragment.
+    pub fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.eat_keyword(keyword) {
+            return Ok(());
+        }
+        Err(expected_here(
+            self.unexpected(format!(
+                "Expected `{}`, found {}",
+                keyword,
+                describe_found(self.peek())
+            )),
+            &format!("`{}`", keyword),
+        ))
+    }
+
+    /// Takes the description in front of a definition, field, argument or
+    /// enum value, if there is one. Descriptions may be block strings or
+    /// `"quoted"` strings.
+    pub fn take_description(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(Token::Description(desc) | Token::String(desc)) => {
+                let desc: String = desc.to_string();
+                self.bump();
+                Some(desc)
+            }
+            _ => None,
+        }
+    }
+
+    /// The index of the current token, for [`Parser::span_from`] and
+    /// [`Parser::reset`].
+    pub fn position(&self) -> usize {
+        self.index
+    }
+
+    /// Moves back (or forward, over already lexed tokens) to `position`,
+    /// which must not have been released.
+    pub fn reset(&mut self, position: usize) {
+        debug_assert!(
+            position >= self.first,
+            "reset to released token {}",
+            position
+        );
+        self.index = position.clamp(self.first, self.end());
+        self.fill();
+    }
+
+    /// Forgets the tokens before `position`. The parser can no longer reset
+    /// to them or compute spans from them, except for the token before the
+    /// current one, which [`Parser::previous`] needs.
+    pub fn release(&mut self, position: usize) {
+        let keep: usize = position.min(self.index.saturating_sub(1));
+        while self.first < keep && self.tokens.pop_front().is_some() {
+            self.first += 1;
+        }
+    }
+
+    /// The span of the token at `position`.
+    pub fn span_at(&self, position: usize) -> Span {
+        self.token(position).map(|t| t.span).unwrap_or_default()
+    }
+
+    /// The span from the token at `start` up to the last token consumed, i.e.
+    /// the span of a node that started at `start`.
+    pub fn span_from(&self, start: usize) -> Span {
+        let first: Span = self.span_at(start);
+        let last: Span = self
+            .index
+            .checked_sub(1)
+            .and_then(|i| self.token(i))
+            .map(|t| t.span)
+            .unwrap_or(first);
+
+        first.to(last)
+    }
+
+    /// Builds an error for the current token, pointing at its span.
+    pub fn unexpected(&self, message: impl Into<String>) -> Diagnostic {
+        self.unexpected_at(self.index, message)
+    }
+
+    /// Builds an error for the token at `position`. When the input ran out,
+    /// the error points just past the last token instead.
+    pub fn unexpected_at(&self, position: usize, message: impl Into<String>) -> Diagnostic {
+        match self.token(position) {
+            Some(token) => Diagnostic::error("unexpected-token", message)
+                .with_primary(token.span, format!("unexpected {}", describe(&token.token))),
+            None => {
+                let end = self.tokens.back().map(|t| t.span.end).unwrap_or_default();
+                Diagnostic::error("unexpected-eof", message)
+                    .with_primary(Span::new(end, end), "input ends here")
+            }
+        }
+    }
+
+    /// Fails unless every token has been consumed.
+    pub fn expect_end(&self) -> Result<()> {
+        match self.peek() {
+            None => Ok(()),
+            Some(other) => {
+                Err(self.unexpected(format!("Expected end of input, found {}", describe(other))))
+            }
+        }
+    }
+
+    /// Removes and returns the errors the lexer reported so far.
+    pub fn take_lex_errors(&mut self) -> Vec<Diagnostic> {
+        std::mem::take(&mut self.lex_errors)
+    }
+
+    /// Removes and returns the comments read so far.
+    pub fn take_comments(&mut self) -> Vec<Trivia> {
+        std::mem::take(&mut self.comments)
+    }
+
+    /// Parses a type reference such as `[String!]!`.
+    pub fn parse_type_ref(&mut self) -> Result<TypeRef> {
+        type_ref::parse_type_ref(self)
+    }
+
+    /// Parses an input value such as `{ limit: 10, tags: ["a"] }`.
+    pub fn parse_value(&mut self) -> Result<Value> {
+        value::parse_value(self)
+    }
+
+    /// Parses a possibly empty list of directives such as `@a @b(c: 1)`.
+    pub fn parse_directives(&mut self) -> Result<Vec<Directive>> {
+        directives::parse_directives(self)
+    }
+}
+
+/// Runs `parse` over the whole of `source`, e.g.
+/// `parse_str("@key(fields: \"id\")", Parser::parse_directives)`. Fails with
+/// every lexer error, or with the parse error, or if input is left over.
+pub fn parse_str<'a, T>(
+    source: &'a str,
+    parse: impl FnOnce(&mut Parser<'a>) -> Result<T>,
+) -> std::result::Result<T, Vec<Diagnostic>> {
+    let mut parser: Parser<'a> = Parser::new(source);
+    let result: Result<T> = parse(&mut parser).and_then(|output| {
+        parser.expect_end()?;
+        Ok(output)
+    });
+
+    let mut errors: Vec<Diagnostic> = parser.take_lex_errors();
+    match result {
+        Ok(output) if errors.is_empty() => Ok(output),
+        Ok(_) => Err(errors),
+        Err(diagnostic) => {
+            errors.push(diagnostic);
+            Err(errors)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::token::spanned;
+
+    #[test]
+    fn test_lexes_on_demand() {
+        let mut parser = Parser::new("type User { id: ID }");
+        assert_eq!(parser.tokens.len(), LOOKAHEAD + 1);
+        assert_eq!(parser.nth(2), Some(&Token::BraceOpen));
+
+        parser.bump();
+        assert_eq!(parser.tokens.len(), LOOKAHEAD + 2);
+        assert_eq!(parser.previous(), Some(&Token::Name("type")));
+    }
+
+    #[test]
+    fn test_releases_consumed_tokens() {
+        let mut parser = Parser::new("scalar A scalar B @tag");
+        parser.bump();
+        parser.bump();
+        let start: usize = parser.position();
+        parser.release(start);
+
+        assert_eq!(parser.tokens.len(), LOOKAHEAD + 2);
+        assert_eq!(parser.previous(), Some(&Token::Name("A")));
+
+        parser.bump();
+        parser.bump();
+        parser.release(start);
+        assert_eq!(parser.tokens.len(), 4, "tokens from `start` on are kept");
+        let span: Span = parser.span_from(start);
+        assert_eq!((span.start.column, span.end.column), (10, 18));
+
+        parser.reset(start);
+        assert_eq!(parser.peek(), Some(&Token::Name("scalar")));
+    }
+
+    #[test]
+    fn test_keywords() {
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Name("on"), Token::Name("User")]));
+
+        assert!(!parser.eat_keyword("implements"));
+        assert!(parser.eat_keyword("on"));
+        let err = parser.expect_keyword("on").unwrap_err();
+        assert_eq!(err.message, "Expected `on`, found name `User`");
+        assert_eq!(parser.position(), 1, "failed expectations consume nothing");
+    }
+
+    #[test]
+    fn test_expect_name() {
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Name("one"), Token::BraceOpen]));
+
+        assert_eq!(parser.expect_name().unwrap(), "one");
+        assert_eq!(parser.position(), 1);
+
+        let err = parser.expect_name().unwrap_err();
+        assert_eq!(err.message, "Expected a name, found `{`");
+        assert_eq!(parser.position(), 1);
+    }
+
+    #[test]
+    fn test_expect_field_name() {
+        let mut parser = Parser::new("true false null");
+
+        assert_eq!(parser.expect_field_name().unwrap(), "true");
+        assert_eq!(parser.expect_field_name().unwrap(), "false");
+        assert_eq!(
+            parser.expect_name().unwrap_err().message,
+            "Expected a name, found `null`"
+        );
+        assert_eq!(parser.expect_field_name().unwrap(), "null");
+    }
+
+    #[test]
+    fn test_expect_at_end_of_input() {
+        let mut parser = Parser::new("type");
+        parser.bump();
+
+        let err = parser.expect(Token::Colon).unwrap_err();
+        assert_eq!(err.code, "unexpected-eof");
+        assert_eq!(err.message, "Expected `:`, found end of input");
+        assert_eq!(err.span().unwrap().start.column, 5);
+    }
+
+    #[test]
+    fn test_span_from() {
+        let mut parser = Parser::new("a  b c");
+        let start = parser.position();
+        parser.bump();
+        parser.bump();
+
+        let span = parser.span_from(start);
+        assert_eq!((span.start.column, span.end.column), (1, 5));
+    }
+
+    #[test]
+    fn test_parse_str() {
+        let type_ref = parse_str("[Int!]", Parser::parse_type_ref).unwrap();
+        assert!(
+            matches!(type_ref, TypeRef::List(ref inner) if matches!(**inner, TypeRef::NonNull(_)))
+        );
+
+        let directives = parse_str("@a @b(c: $d)", Parser::parse_directives).unwrap();
+        assert_eq!(directives.len(), 2);
+
+        let errors = parse_str("RED BLUE", Parser::parse_value).unwrap_err();
+        assert_eq!(
+            errors[0].message,
+            "Expected end of input, found name `BLUE`"
+        );
+
+        let errors = parse_str("[Int § ]", Parser::parse_type_ref).unwrap_err();
+        assert_eq!(errors[0].message, "Unexpected character: '§'");
+    }
+}
@@ -1,9 +1,14 @@
+use std::borrow::Cow;
+
+use graphqlgen_schema::ast::Span;
+
+/// A lexed token. Names and strings borrow from the source where possible.
 #[derive(Debug, Clone, PartialEq)]
-pub enum Token {
-    Name(String),
+pub enum Token<'a> {
+    Name(&'a str),
     Int(i64),
     Float(f64),
-    String(String),
+    String(Cow<'a, str>),
     Boolean(bool),
     Null,
 
@@ -23,5 +28,30 @@ pub enum Token {
     Ampersand,
     EOF,
     Comma,
-    Description(String),
+    Description(Cow<'a, str>),
+    /// The text after a `#`. Only produced by [`Lexer::with_comments`].
+    ///
+    /// [`Lexer::with_comments`]: super::lexers::Lexer::with_comments
+    Comment(&'a str),
+}
+
+/// A token together with the source range it was read from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpannedToken<'a> {
+    pub token: Token<'a>,
+    pub span: Span,
+}
+
+impl<'a> From<Token<'a>> for SpannedToken<'a> {
+    fn from(token: Token<'a>) -> Self {
+        Self {
+            token,
+            span: Span::default(),
+        }
+    }
+}
+
+/// Wraps bare tokens with dummy spans, for building token streams by hand.
+pub fn spanned(tokens: Vec<Token<'_>>) -> Vec<SpannedToken<'_>> {
+    tokens.into_iter().map(SpannedToken::from).collect()
 }
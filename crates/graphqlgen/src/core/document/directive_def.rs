@@ -0,0 +1,196 @@
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{Comments, Definition, DirectiveDef, DirectiveLocation, InputValue};
+
+use crate::core::common::{
+    fields::parse_arguments_definition::parse_arguments_definition,
+    parse::expect::{describe_found, expected_here},
+    parser::Parser,
+    token::Token,
+};
+
+pub fn parse_directive_def(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    let start: usize = parser.position();
+    parser.bump();
+
+    parser.expect(Token::At)?;
+    let name: String = parser.expect_name()?;
+
+    let arguments: Option<Vec<InputValue>> = if parser.at(&Token::ParenOpen) {
+        Some(parse_arguments_definition(parser)?)
+    } else {
+        None
+    };
+
+    let repeatable: bool = parser.eat_keyword("repeatable");
+
+    if !parser.eat_keyword("on") {
+        return Err(expected_here(
+            parser.unexpected(format!(
+                "Expected `on`, found {}",
+                describe_found(parser.peek())
+            )),
+            "`on` followed by the directive's locations",
+        ));
+    }
+
+    // A leading `|` is allowed before the first location.
+    parser.eat(&Token::Pipe);
+
+    let mut locations: Vec<DirectiveLocation> = vec![parse_location(parser)?];
+    while parser.eat(&Token::Pipe) {
+        locations.push(parse_location(parser)?);
+    }
+
+    Ok(Definition::Directive(DirectiveDef {
+        name,
+        arguments,
+        repeatable,
+        locations,
+        description,
+        built_in: false,
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    }))
+}
+
+fn parse_location(parser: &mut Parser) -> Result<DirectiveLocation> {
+    let start: usize = parser.position();
+    let name: String = parser.expect_name()?;
+
+    DirectiveLocation::from_name(&name).ok_or_else(|| {
+        parser
+            .unexpected_at(start, format!("Unknown directive location '{}'", name))
+            .with_help("locations are written in upper case, e.g. `FIELD_DEFINITION`")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
+    use graphqlgen_schema::ast::{TypeRef, Value};
+
+    #[test]
+    fn test_parse_directive_def() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("directive"),
+            Token::At,
+            Token::Name("auth"),
+            Token::ParenOpen,
+            Token::Name("requires"),
+            Token::Colon,
+            Token::Name("Role"),
+            Token::Equals,
+            Token::Name("ADMIN"),
+            Token::ParenClose,
+            Token::Name("on"),
+            Token::Name("OBJECT"),
+            Token::Pipe,
+            Token::Name("FIELD_DEFINITION"),
+        ]));
+
+        let result = parse_directive_def(&mut parser, None).unwrap();
+        assert_eq!(parser.position(), 14);
+
+        if let Definition::Directive(directive) = result {
+            assert_eq!(directive.name, "auth");
+            assert!(!directive.repeatable);
+            assert_eq!(
+                directive.locations,
+                vec![
+                    DirectiveLocation::Object,
+                    DirectiveLocation::FieldDefinition
+                ]
+            );
+
+            let args = directive.arguments.unwrap();
+            assert_eq!(args.len(), 1);
+            assert_eq!(args[0].name, "requires");
+            assert!(matches!(args[0].value_type, TypeRef::Named(ref n) if *n == "Role"));
+            assert_eq!(args[0].default_value, Some(Value::Enum("ADMIN".into())));
+        } else {
+            panic!("Expected Definition::Directive");
+        }
+    }
+
+    #[test]
+    fn test_parse_repeatable_directive_with_leading_pipe() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("directive"),
+            Token::At,
+            Token::Name("tag"),
+            Token::Name("repeatable"),
+            Token::Name("on"),
+            Token::Pipe,
+            Token::Name("QUERY"),
+            Token::Pipe,
+            Token::Name("INPUT_FIELD_DEFINITION"),
+        ]));
+
+        let result = parse_directive_def(&mut parser, Some("Tags".into())).unwrap();
+
+        if let Definition::Directive(directive) = result {
+            assert!(directive.repeatable);
+            assert!(directive.arguments.is_none());
+            assert_eq!(directive.description.as_deref(), Some("Tags"));
+            assert_eq!(
+                directive.locations,
+                vec![
+                    DirectiveLocation::Query,
+                    DirectiveLocation::InputFieldDefinition
+                ]
+            );
+            assert!(directive.locations[0].is_executable());
+            assert!(!directive.locations[1].is_executable());
+        } else {
+            panic!("Expected Definition::Directive");
+        }
+    }
+
+    #[test]
+    fn test_parse_directive_def_unknown_location() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("directive"),
+            Token::At,
+            Token::Name("tag"),
+            Token::Name("on"),
+            Token::Name("FIELDS"),
+        ]));
+
+        let result = parse_directive_def(&mut parser, None);
+        assert_eq!(
+            result.unwrap_err().message,
+            "Unknown directive location 'FIELDS'"
+        );
+    }
+
+    #[test]
+    fn test_parse_directive_def_missing_on() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("directive"),
+            Token::At,
+            Token::Name("tag"),
+            Token::Name("FIELD"),
+        ]));
+
+        let result = parse_directive_def(&mut parser, None);
+        assert!(result.unwrap_err().message.starts_with("Expected `on`"));
+    }
+
+    #[test]
+    fn test_parse_directive_def_missing_at() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("directive"),
+            Token::Name("tag"),
+            Token::Name("on"),
+            Token::Name("FIELD"),
+        ]));
+
+        let result = parse_directive_def(&mut parser, None);
+        assert!(result
+            .unwrap_err()
+            .message
+            .starts_with("Expected `@`, found name `tag`"));
+    }
+}
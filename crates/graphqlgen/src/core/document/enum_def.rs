@@ -1,64 +1,73 @@
-use anyhow::Result;
-use graphqlgen_schema::ast::{Definition, EnumDef, EnumValue};
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{Comments, Definition, EnumDef, EnumValue};
 
 use crate::core::common::{
     parse::{
-        directives::parse_directives,
-        expect::{expect_name, expect_token},
+        directives::{deprecation, parse_const_directives},
+        expect::describe,
     },
+    parser::Parser,
     token::Token,
 };
 
-pub fn parse_enum(
-    tokens: &[Token],
-    index: &mut usize,
-    description: Option<String>,
-) -> Result<Definition> {
-    *index += 1;
+pub fn parse_enum(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    parse_enum_def(parser, description).map(Definition::Enum)
+}
 
-    let name: String = expect_name(tokens, index)?;
-    let directives: Vec<graphqlgen_schema::ast::Directive> = parse_directives(tokens, index)?;
+/// Parses `enum Name @directives { VALUES }`. The values are optional, as for
+/// object types.
+pub fn parse_enum_def(parser: &mut Parser, description: Option<String>) -> Result<EnumDef> {
+    let start: usize = parser.position();
+    parser.bump();
 
-    expect_token(tokens, index, Token::BraceOpen)?;
+    let name: String = parser.expect_name()?;
+    let directives: Vec<graphqlgen_schema::ast::Directive> = parse_const_directives(parser)?;
 
     let mut values: Vec<EnumValue> = Vec::new();
+    let mut sub_description: Option<String> = None;
 
-    while *index < tokens.len() {
-        let mut sub_description: Option<String> = None;
+    let has_body: bool = parser.eat(&Token::BraceOpen);
 
-        match tokens.get(*index) {
+    while has_body && !parser.at_end() {
+        match parser.peek() {
             Some(Token::BraceClose) => {
-                *index += 1;
+                parser.bump();
                 break;
             }
 
-            Some(Token::Description(desc)) => {
-                sub_description = Some(desc.clone());
-                *index += 1;
+            Some(Token::Description(_) | Token::String(_)) => {
+                sub_description = parser.take_description();
             }
 
             Some(Token::Name(_)) => {
-                let name: String = expect_name(tokens, index)?;
-                let directives = parse_directives(tokens, index)?;
+                let value_start: usize = parser.position();
+                let name: String = parser.expect_name()?;
+                let directives = parse_const_directives(parser)?;
                 values.push(EnumValue {
                     name,
-                    description: sub_description,
+                    description: sub_description.take(),
+                    deprecation: deprecation(&directives),
                     directives: if directives.is_empty() {
                         None
                     } else {
                         Some(directives)
                     },
+                    span: parser.span_from(value_start),
+                    comments: Comments::default(),
                 });
             }
 
-            _ => {
-                println!("Unexpected token in enum definition: {:?}", tokens[*index]);
-                break;
+            Some(other) => {
+                return Err(
+                    parser.unexpected(format!("Unexpected {} in enum definition", describe(other)))
+                );
             }
+
+            None => break,
         }
     }
 
-    Ok(Definition::Enum(EnumDef {
+    Ok(EnumDef {
         name,
         values,
         directives: if directives.is_empty() {
@@ -67,28 +76,31 @@ pub fn parse_enum(
             Some(directives)
         },
         description,
-    }))
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
 
     #[test]
     fn test_parse_simple_enum() {
-        let tokens = vec![
-            Token::Name("enum".into()),
-            Token::Name("Role".into()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("enum"),
+            Token::Name("Role"),
             Token::BraceOpen,
-            Token::Name("ADMIN".into()),
-            Token::Name("USER".into()),
-            Token::Name("GUEST".into()),
+            Token::Name("ADMIN"),
+            Token::Name("USER"),
+            Token::Name("GUEST"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_enum(&tokens, &mut index, None).unwrap();
-        assert_eq!(index, 7);
+        let result = parse_enum(&mut parser, None).unwrap();
+        assert_eq!(parser.position(), 7);
 
         if let Definition::Enum(enum_def) = result {
             assert_eq!(enum_def.name, "Role");
@@ -103,18 +115,17 @@ mod tests {
 
     #[test]
     fn test_parse_enum_with_directives() {
-        let tokens = vec![
-            Token::Name("enum".into()),
-            Token::Name("Status".into()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("enum"),
+            Token::Name("Status"),
             Token::At,
-            Token::Name("deprecated".into()),
+            Token::Name("deprecated"),
             Token::BraceOpen,
-            Token::Name("ACTIVE".into()),
-            Token::Name("INACTIVE".into()),
+            Token::Name("ACTIVE"),
+            Token::Name("INACTIVE"),
             Token::BraceClose,
-        ];
-        let mut index = 0;
-        let result = parse_enum(&tokens, &mut index, None).unwrap();
+        ]));
+        let result = parse_enum(&mut parser, None).unwrap();
 
         if let Definition::Enum(enum_def) = result {
             assert_eq!(enum_def.name, "Status");
@@ -125,55 +136,68 @@ mod tests {
 
     #[test]
     fn test_parse_enum_values_with_directives() {
-        let tokens = vec![
-            Token::Name("enum".into()),
-            Token::Name("Color".into()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("enum"),
+            Token::Name("Color"),
             Token::BraceOpen,
-            Token::Name("RED".into()),
+            Token::Name("RED"),
             Token::At,
-            Token::Name("deprecated".into()),
+            Token::Name("deprecated"),
             Token::ParenOpen,
-            Token::Name("reason".into()),
+            Token::Name("reason"),
             Token::Colon,
             Token::String("use CRIMSON".into()),
             Token::ParenClose,
-            Token::Name("BLUE".into()),
+            Token::Name("BLUE"),
             Token::BraceClose,
-        ];
-        let mut index = 0;
-        let result = parse_enum(&tokens, &mut index, None).unwrap();
+        ]));
+        let result = parse_enum(&mut parser, None).unwrap();
 
         if let Definition::Enum(enum_def) = result {
             assert_eq!(enum_def.name, "Color");
             assert_eq!(enum_def.values.len(), 2);
             assert_eq!(enum_def.values[0].name, "RED");
             assert!(enum_def.values[0].directives.is_some());
+            assert_eq!(
+                enum_def.values[0].deprecation.as_ref().unwrap().reason,
+                "use CRIMSON"
+            );
             assert_eq!(enum_def.values[1].name, "BLUE");
+            assert!(enum_def.values[1].deprecation.is_none());
         }
     }
 
     #[test]
     fn test_invalid_enum_missing_name() {
-        let tokens = vec![Token::Name("enum".into())];
-        let mut index = 0;
-        let result = parse_enum(&tokens, &mut index, None);
+        let mut parser = Parser::from_tokens(spanned(vec![Token::Name("enum")]));
+        let result = parse_enum(&mut parser, None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_invalid_enum_value_keyword() {
+        let mut parser = Parser::new("enum Flag { ON true }");
+        let result = parse_enum(&mut parser, None);
+        assert_eq!(
+            result.unwrap_err().message,
+            "Unexpected `true` in enum definition"
+        );
+    }
+
     #[test]
     fn test_enum_with_comments() {
-        let tokens = vec![
+        let mut parser = Parser::from_tokens(spanned(vec![
             Token::Description(("A enum with comments").into()),
-            Token::Name("enum".into()),
-            Token::Name("CommentedEnum".into()),
+            Token::Name("enum"),
+            Token::Name("CommentedEnum"),
             Token::BraceOpen,
             Token::Description("This is a comment".into()),
-            Token::Name("VALUE1".into()),
-            Token::Name("VALUE2".into()),
+            Token::Name("VALUE1"),
+            Token::Name("VALUE2"),
             Token::BraceClose,
-        ];
-        let mut index = 0;
-        let result = parse_enum(&tokens, &mut index, None).unwrap();
+        ]));
+        let description = parser.take_description();
+        let result = parse_enum(&mut parser, description).unwrap();
 
         if let Definition::Enum(enum_def) = result {
             assert_eq!(enum_def.description, Some("A enum with comments".into()));
@@ -187,4 +211,21 @@ mod tests {
             assert_eq!(enum_def.values[1].name, "VALUE2");
         }
     }
+
+    #[test]
+    fn test_enum_value_with_string_description() {
+        let mut parser = Parser::new(r#"enum Role { "Full access" ADMIN """Read only""" GUEST }"#);
+        let result = parse_enum(&mut parser, None).unwrap();
+
+        if let Definition::Enum(enum_def) = result {
+            let descriptions: Vec<Option<&str>> = enum_def
+                .values
+                .iter()
+                .map(|v| v.description.as_deref())
+                .collect();
+            assert_eq!(descriptions, vec![Some("Full access"), Some("Read only")]);
+        } else {
+            panic!("Expected EnumDef");
+        }
+    }
 }
@@ -0,0 +1,198 @@
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{Definition, Extension, Span};
+
+use crate::core::common::{
+    parse::expect::{describe_found, expected_here},
+    parser::Parser,
+    token::Token,
+};
+
+use super::{enum_def, input, interface, scalar, schema, type_def, union};
+
+/// Parses `extend type ...`, `extend enum ...` and the other extensions. The
+/// body of an extension is optional, but it must add something.
+pub fn parse_extension(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    let start: usize = parser.position();
+    if description.is_some() {
+        return Err(parser
+            .unexpected_at(
+                start - 1,
+                "Extensions cannot have a description".to_string(),
+            )
+            .with_help("move the description to the definition being extended"));
+    }
+    parser.bump();
+
+    let mut extension: Extension = match parser.peek() {
+        Some(Token::Name(keyword)) if *keyword == "type" => {
+            Extension::Type(type_def::parse_type_def(parser, None)?)
+        }
+        Some(Token::Name(keyword)) if *keyword == "input" => {
+            Extension::Input(input::parse_input_def(parser, None)?)
+        }
+        Some(Token::Name(keyword)) if *keyword == "interface" => {
+            Extension::Interface(interface::parse_interface_def(parser, None)?)
+        }
+        Some(Token::Name(keyword)) if *keyword == "scalar" => {
+            Extension::Scalar(scalar::parse_scalar_def(parser, None)?)
+        }
+        Some(Token::Name(keyword)) if *keyword == "union" => {
+            Extension::Union(union::parse_union_def(parser, None, false)?)
+        }
+        Some(Token::Name(keyword)) if *keyword == "enum" => {
+            Extension::Enum(enum_def::parse_enum_def(parser, None)?)
+        }
+        Some(Token::Name(keyword)) if *keyword == "schema" => {
+            Extension::Schema(schema::parse_schema_def(parser, None, false)?)
+        }
+        other => {
+            return Err(expected_here(
+                parser.unexpected(format!(
+                    "Expected a definition to extend, found {}",
+                    describe_found(other)
+                )),
+                "`type`, `interface`, `input`, `enum`, `union`, `scalar` or `schema`",
+            ))
+        }
+    };
+
+    set_span(&mut extension, parser.span_from(start));
+
+    if adds_nothing(&extension) {
+        return Err(parser
+            .unexpected(format!(
+                "Extension of {} adds nothing",
+                extension
+                    .name()
+                    .map(|name| format!("'{}'", name))
+                    .unwrap_or_else(|| "the schema".to_string())
+            ))
+            .with_help("an extension must add directives, fields, interfaces, values or members"));
+    }
+
+    Ok(Definition::Extension(extension))
+}
+
+/// Makes the span start at `extend` rather than at the keyword after it.
+fn set_span(extension: &mut Extension, span: Span) {
+    match extension {
+        Extension::Type(ext) | Extension::Interface(ext) => ext.span = span,
+        Extension::Input(ext) => ext.span = span,
+        Extension::Scalar(ext) => ext.span = span,
+        Extension::Union(ext) => ext.span = span,
+        Extension::Enum(ext) => ext.span = span,
+        Extension::Schema(ext) => ext.span = span,
+    }
+}
+
+fn adds_nothing(extension: &Extension) -> bool {
+    match extension {
+        Extension::Type(ext) | Extension::Interface(ext) => {
+            ext.fields.is_empty() && ext.interfaces.is_empty() && ext.directives.is_none()
+        }
+        Extension::Input(ext) => ext.fields.is_empty() && ext.directives.is_none(),
+        Extension::Scalar(ext) => ext.directives.is_none(),
+        Extension::Union(ext) => ext.members.is_empty() && ext.directives.is_none(),
+        Extension::Enum(ext) => ext.values.is_empty() && ext.directives.is_none(),
+        Extension::Schema(ext) => ext.operation_types.is_empty() && ext.directives.is_none(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use graphqlgen_schema::ast::OperationType;
+
+    fn parse(source: &str) -> Result<Definition> {
+        let mut parser = Parser::new(source);
+        let result = parse_extension(&mut parser, None);
+        if result.is_ok() {
+            assert!(parser.at_end());
+        }
+        result
+    }
+
+    #[test]
+    fn test_parse_type_extension() {
+        let result = parse("extend type Query @cached { me: User }").unwrap();
+
+        if let Definition::Extension(Extension::Type(ext)) = result {
+            assert_eq!(ext.name, "Query");
+            assert_eq!(ext.fields[0].name, "me");
+            assert_eq!(ext.directives.unwrap()[0].name, "cached");
+            assert_eq!(ext.span.start.column, 1);
+        } else {
+            panic!("Expected Extension::Type");
+        }
+    }
+
+    #[test]
+    fn test_parse_extension_adding_interfaces() {
+        let result = parse("extend type User implements Node & Entity").unwrap();
+
+        if let Definition::Extension(Extension::Type(ext)) = result {
+            assert_eq!(ext.interfaces, vec!["Node", "Entity"]);
+            assert!(ext.fields.is_empty());
+        } else {
+            panic!("Expected Extension::Type");
+        }
+    }
+
+    #[test]
+    fn test_parse_extension_without_body() {
+        let result = parse("extend interface Node @key(fields: \"id\")").unwrap();
+
+        if let Definition::Extension(Extension::Interface(ext)) = result {
+            assert!(ext.fields.is_empty());
+            assert_eq!(ext.directives.unwrap().len(), 1);
+        } else {
+            panic!("Expected Extension::Interface");
+        }
+    }
+
+    #[test]
+    fn test_parse_other_extensions() {
+        let extensions = [
+            ("extend scalar Date @format", "scalar"),
+            ("extend input Filter { limit: Int }", "input"),
+            ("extend union Result = Photo | Video", "union"),
+            ("extend enum Role { GUEST }", "enum"),
+            ("extend schema { subscription: Events }", "schema"),
+        ];
+
+        for (source, keyword) in extensions {
+            match parse(source) {
+                Ok(Definition::Extension(ext)) => assert_eq!(ext.keyword(), keyword),
+                other => panic!("{}: {:?}", source, other),
+            }
+        }
+
+        if let Ok(Definition::Extension(Extension::Schema(ext))) =
+            parse("extend schema { subscription: Events }")
+        {
+            assert_eq!(
+                ext.operation_types[0].operation,
+                OperationType::Subscription
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_empty_extension() {
+        let result = parse("extend enum Role");
+        assert_eq!(
+            result.unwrap_err().message,
+            "Extension of 'Role' adds nothing"
+        );
+    }
+
+    #[test]
+    fn test_parse_extension_of_directive() {
+        let result = parse("extend directive @auth on FIELD");
+        assert!(result
+            .unwrap_err()
+            .message
+            .starts_with("Expected a definition to extend"));
+    }
+}
@@ -1,29 +1,30 @@
-use anyhow::Result;
-use graphqlgen_schema::ast::{Definition, Directive, Field, TypeDef};
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{Comments, Definition, Directive, InputObjectDef, InputValue};
 
 use crate::core::common::{
-    fields::parse_fields::parse_fields,
-    parse::{
-        directives::parse_directives,
-        expect::{expect_name, expect_token},
-    },
-    token::Token,
+    fields::parse_input_fields::parse_input_fields, parse::directives::parse_const_directives,
+    parser::Parser, token::Token,
 };
 
-pub fn parse_input(
-    tokens: &[Token],
-    index: &mut usize,
-    description: Option<String>,
-) -> Result<Definition> {
-    *index += 1;
-    let input_name: String = expect_name(&tokens, index)?;
-    let directives: Vec<Directive> = parse_directives(&tokens, index)?;
-
-    expect_token(&tokens, index, Token::BraceOpen)?;
-
-    let fields: Vec<Field> = parse_fields(tokens, index)?;
+pub fn parse_input(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    parse_input_def(parser, description).map(Definition::Input)
+}
 
-    Ok(Definition::Input(TypeDef {
+/// Parses `input Name @directives { fields }`, where fields may have default
+/// values. The fields are optional, as for object types.
+pub fn parse_input_def(parser: &mut Parser, description: Option<String>) -> Result<InputObjectDef> {
+    let start: usize = parser.position();
+    parser.bump();
+    let input_name: String = parser.expect_name()?;
+    let directives: Vec<Directive> = parse_const_directives(parser)?;
+
+    let fields: Vec<InputValue> = if parser.at(&Token::BraceOpen) {
+        parse_input_fields(parser)?
+    } else {
+        Vec::new()
+    };
+
+    Ok(InputObjectDef {
         name: input_name,
         fields,
         directives: if directives.is_empty() {
@@ -32,30 +33,33 @@ pub fn parse_input(
             Some(directives)
         },
         description,
-    }))
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
 
     #[test]
     fn test_parse_input() {
-        let tokens: Vec<Token> = vec![
-            Token::Name("input".to_string()),
-            Token::Name("MyInput".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("input"),
+            Token::Name("MyInput"),
             Token::BraceOpen,
-            Token::Name("field1".to_string()),
+            Token::Name("field1"),
             Token::Colon,
-            Token::Name("String".to_string()),
-            Token::Name("field2".to_string()),
+            Token::Name("String"),
+            Token::Name("field2"),
             Token::Colon,
-            Token::Name("Int".to_string()),
+            Token::Name("Int"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index: usize = 0;
-        let result: Definition = parse_input(&tokens, &mut index, None).unwrap();
+        let result: Definition = parse_input(&mut parser, None).unwrap();
 
         if let Definition::Input(input) = result {
             assert_eq!(input.name, "MyInput");
@@ -66,33 +70,31 @@ mod tests {
 
     #[test]
     fn test_parse_input_invalid() {
-        let tokens: Vec<Token> = vec![
-            Token::Name("input".to_string()),
-            Token::Name("MyInput".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("input"),
+            Token::Name("MyInput"),
             Token::BraceOpen,
-            Token::Name("field1".to_string()),
+            Token::Name("field1"),
             Token::Colon,
-            Token::Name("String".to_string()),
+            Token::Name("String"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index: usize = 0;
-        let result: Result<Definition> = parse_input(&tokens, &mut index, None);
+        let result: Result<Definition> = parse_input(&mut parser, None);
 
         assert!(result.is_ok(), "Expected an error but got: {:?}", result);
     }
 
     #[test]
     fn test_parse_input_empty() {
-        let tokens: Vec<Token> = vec![
-            Token::Name("input".to_string()),
-            Token::Name("MyInput".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("input"),
+            Token::Name("MyInput"),
             Token::BraceOpen,
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index: usize = 0;
-        let result: Definition = parse_input(&tokens, &mut index, None).unwrap();
+        let result: Definition = parse_input(&mut parser, None).unwrap();
 
         if let Definition::Input(input) = result {
             assert_eq!(input.name, "MyInput");
@@ -107,23 +109,22 @@ mod tests {
 
     #[test]
     fn test_parse_input_with_directives() {
-        let tokens: Vec<Token> = vec![
-            Token::Name("input".to_string()),
-            Token::Name("MyInput".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("input"),
+            Token::Name("MyInput"),
             Token::At,
-            Token::Name("directive".to_string()),
+            Token::Name("directive"),
             Token::BraceOpen,
-            Token::Name("field1".to_string()),
+            Token::Name("field1"),
             Token::Colon,
-            Token::Name("String".to_string()),
-            Token::Name("field2".to_string()),
+            Token::Name("String"),
+            Token::Name("field2"),
             Token::Colon,
-            Token::Name("Int".to_string()),
+            Token::Name("Int"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index: usize = 0;
-        let result: Definition = parse_input(&tokens, &mut index, None).unwrap();
+        let result: Definition = parse_input(&mut parser, None).unwrap();
 
         if let Definition::Input(input) = result {
             assert_eq!(input.name, "MyInput");
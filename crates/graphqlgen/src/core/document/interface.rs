@@ -1,29 +1,35 @@
-use anyhow::Result;
-use graphqlgen_schema::ast::{Definition, TypeDef};
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{Comments, Definition, TypeDef};
 
 use crate::core::common::{
     fields::parse_fields::parse_fields,
-    parse::{
-        directives::parse_directives,
-        expect::{expect_name, expect_token},
-    },
+    parse::{directives::parse_const_directives, implements::parse_implements},
+    parser::Parser,
     token::Token,
 };
 
-pub fn parse_interface(
-    tokens: &[Token],
-    index: &mut usize,
-    description: Option<String>,
-) -> Result<Definition> {
-    *index += 1;
-    let name = expect_name(tokens, index)?;
-    let directives = parse_directives(tokens, index)?;
-    expect_token(tokens, index, Token::BraceOpen)?;
+pub fn parse_interface(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    parse_interface_def(parser, description).map(Definition::Interface)
+}
+
+/// Parses `interface Name implements A & B @directives { fields }`. The fields
+/// are optional, as for object types.
+pub fn parse_interface_def(parser: &mut Parser, description: Option<String>) -> Result<TypeDef> {
+    let start: usize = parser.position();
+    parser.bump();
+    let name = parser.expect_name()?;
+    let interfaces: Vec<String> = parse_implements(parser)?;
+    let directives = parse_const_directives(parser)?;
 
-    let fields = parse_fields(tokens, index)?;
+    let fields = if parser.eat(&Token::BraceOpen) {
+        parse_fields(parser)?
+    } else {
+        Vec::new()
+    };
 
-    Ok(Definition::Interface(TypeDef {
+    Ok(TypeDef {
         name,
+        interfaces,
         fields,
         directives: if directives.is_empty() {
             None
@@ -31,35 +37,38 @@ pub fn parse_interface(
             Some(directives)
         },
         description,
-    }))
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
     use graphqlgen_schema::ast::{Definition, TypeDef, TypeRef, Value};
 
     #[test]
     fn test_parse_interface_basic() {
-        let tokens: Vec<Token> = vec![
-            Token::Name("interface".to_string()),
-            Token::Name("Node".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("interface"),
+            Token::Name("Node"),
             Token::BraceOpen,
-            Token::Name("id".to_string()),
+            Token::Name("id"),
             Token::Colon,
-            Token::Name("ID".to_string()),
+            Token::Name("ID"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_interface(&tokens, &mut index, None);
+        let result = parse_interface(&mut parser, None);
         assert!(result.is_ok(), "Expected Ok, got {:?}", result);
 
         if let Definition::Interface(TypeDef {
             name,
             fields,
             directives,
-            description: _,
+            ..
         }) = result.unwrap()
         {
             assert_eq!(name, "Node");
@@ -77,32 +86,31 @@ mod tests {
 
     #[test]
     fn test_parse_interface_with_directives() {
-        let tokens = vec![
-            Token::Name("interface".to_string()),
-            Token::Name("Searchable".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("interface"),
+            Token::Name("Searchable"),
             Token::At,
-            Token::Name("someDirective".to_string()),
+            Token::Name("someDirective"),
             Token::ParenOpen,
-            Token::Name("arg".to_string()),
+            Token::Name("arg"),
             Token::Colon,
-            Token::String("value".to_string()),
+            Token::String("value".into()),
             Token::ParenClose,
             Token::BraceOpen,
-            Token::Name("query".to_string()),
+            Token::Name("query"),
             Token::Colon,
-            Token::Name("String".to_string()),
+            Token::Name("String"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_interface(&tokens, &mut index, None);
+        let result = parse_interface(&mut parser, None);
         assert!(result.is_ok(), "Expected Ok, got {:?}", result);
 
         if let Definition::Interface(TypeDef {
             name,
             fields,
             directives,
-            description: _,
+            ..
         }) = result.unwrap()
         {
             assert_eq!(name, "Searchable");
@@ -116,27 +124,26 @@ mod tests {
             let args = directives[0].arguments.as_ref().unwrap();
             assert_eq!(args.len(), 1);
             assert_eq!(args[0].name, "arg");
-            assert!(matches!(args[0].default_value, Some(Value::String(ref v)) if v == "value"));
+            assert!(matches!(args[0].value, Value::String(ref v) if v == "value"));
         }
     }
 
     #[test]
     fn test_parse_interface_with_multiple_fields() {
-        let tokens = vec![
-            Token::Name("interface".to_string()),
-            Token::Name("Thing".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("interface"),
+            Token::Name("Thing"),
             Token::BraceOpen,
-            Token::Name("id".to_string()),
+            Token::Name("id"),
             Token::Colon,
-            Token::Name("ID".to_string()),
-            Token::Name("title".to_string()),
+            Token::Name("ID"),
+            Token::Name("title"),
             Token::Colon,
-            Token::Name("String".to_string()),
+            Token::Name("String"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_interface(&tokens, &mut index, None);
+        let result = parse_interface(&mut parser, None);
         assert!(result.is_ok());
 
         if let Definition::Interface(TypeDef { name, fields, .. }) = result.unwrap() {
@@ -147,71 +154,95 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_interface_implementing_interfaces() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("interface"),
+            Token::Name("Image"),
+            Token::Name("implements"),
+            Token::Ampersand,
+            Token::Name("Node"),
+            Token::Ampersand,
+            Token::Name("Resource"),
+            Token::BraceOpen,
+            Token::Name("url"),
+            Token::Colon,
+            Token::Name("String"),
+            Token::BraceClose,
+        ]));
+
+        let result = parse_interface(&mut parser, None).unwrap();
+
+        if let Definition::Interface(TypeDef {
+            name, interfaces, ..
+        }) = result
+        {
+            assert_eq!(name, "Image");
+            assert_eq!(interfaces, vec!["Node", "Resource"]);
+        } else {
+            panic!("Expected Definition::Interface");
+        }
+    }
+
     #[test]
     fn test_parse_interface_missing_name() {
-        let tokens = vec![
-            Token::Name("interface".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("interface"),
             Token::Colon,
             Token::BraceOpen,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_interface(&tokens, &mut index, None);
+        let result = parse_interface(&mut parser, None);
         assert!(result.is_err(), "Expected error for missing name");
         let msg = result.unwrap_err().to_string();
-        assert!(msg.contains("Expected name"));
+        assert!(msg.contains("Expected a name"));
     }
 
     #[test]
-    fn test_parse_interface_missing_brace() {
-        let tokens = vec![
-            Token::Name("interface".to_string()),
-            Token::Name("Node".to_string()),
-        ];
-
-        let mut index = 0;
-        let result = parse_interface(&tokens, &mut index, None);
-        assert!(result.is_err());
-        let msg = result.unwrap_err().to_string();
-        assert!(msg.contains("Expected BraceOpen, got None"));
+    fn test_parse_interface_without_body() {
+        let mut parser =
+            Parser::from_tokens(spanned(vec![Token::Name("interface"), Token::Name("Node")]));
+
+        let result = parse_interface_def(&mut parser, None).unwrap();
+        assert_eq!(result.name, "Node");
+        assert!(result.fields.is_empty());
+        assert!(parser.at_end());
     }
 
     #[test]
     fn test_parse_interface_with_unexpected_token() {
-        let tokens = vec![
-            Token::Name("interface".to_string()),
-            Token::Name("Broken".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("interface"),
+            Token::Name("Broken"),
             Token::BraceOpen,
             Token::Colon, // Unexpected token
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_interface(&tokens, &mut index, None);
+        let result = parse_interface(&mut parser, None);
         assert!(result.is_err(), "Expected parse failure");
         let msg = result.unwrap_err().to_string();
-        assert!(msg.contains("Unexpected token in fields"));
+        assert!(msg.contains("Unexpected `:` in fields"));
     }
 
     #[test]
     fn test_parse_interface_with_field_arguments() {
-        let tokens = vec![
-            Token::Name("interface".to_string()),
-            Token::Name("Query".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("interface"),
+            Token::Name("Query"),
             Token::BraceOpen,
-            Token::Name("find".to_string()),
+            Token::Name("find"),
             Token::ParenOpen,
-            Token::Name("id".to_string()),
+            Token::Name("id"),
             Token::Colon,
-            Token::Name("ID".to_string()),
+            Token::Name("ID"),
             Token::ParenClose,
             Token::Colon,
-            Token::Name("Result".to_string()),
+            Token::Name("Result"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_interface(&tokens, &mut index, None);
+        let result = parse_interface(&mut parser, None);
         assert!(result.is_ok());
 
         if let Definition::Interface(TypeDef { name, fields, .. }) = result.unwrap() {
@@ -1,8 +1,12 @@
+pub mod directive_def;
 pub mod enum_def;
+pub mod extension;
 pub mod input;
 pub mod interface;
 pub mod scalar;
+pub mod schema;
 pub mod type_def;
 pub mod union;
 
+use crate::core::common::parser;
 use crate::core::common::token;
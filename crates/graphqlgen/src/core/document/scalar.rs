@@ -1,20 +1,21 @@
-use anyhow::Result;
-use graphqlgen_schema::ast::{Definition, ScalarDef};
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{Comments, Definition, ScalarDef};
 
-use crate::core::common::parse::{directives::parse_directives, expect::expect_name};
+use crate::core::common::parse::directives::parse_const_directives;
 
-use super::token::Token;
+use super::parser::Parser;
 
-pub fn parse_scalar(
-    tokens: &[Token],
-    index: &mut usize,
-    description: Option<String>,
-) -> Result<Definition> {
-    *index += 1;
-    let scalar_name = expect_name(&tokens, index)?;
-    let directives = parse_directives(&tokens, index)?;
+pub fn parse_scalar(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    parse_scalar_def(parser, description).map(Definition::Scalar)
+}
+
+pub fn parse_scalar_def(parser: &mut Parser, description: Option<String>) -> Result<ScalarDef> {
+    let start: usize = parser.position();
+    parser.bump();
+    let scalar_name = parser.expect_name()?;
+    let directives = parse_const_directives(parser)?;
 
-    Ok(Definition::Scalar(ScalarDef {
+    Ok(ScalarDef {
         name: scalar_name,
         directives: if directives.is_empty() {
             None
@@ -22,28 +23,28 @@ pub fn parse_scalar(
             Some(directives)
         },
         description,
-    }))
+        built_in: false,
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::{spanned, Token};
 
     #[test]
     fn test_parse_scalar_basic() {
-        let tokens = vec![
-            Token::Name("scalar".to_string()),
-            Token::Name("Date".to_string()),
-        ];
-        let mut index = 0;
-        let result = parse_scalar(&tokens, &mut index, None);
+        let mut parser =
+            Parser::from_tokens(spanned(vec![Token::Name("scalar"), Token::Name("Date")]));
+        let result = parse_scalar(&mut parser, None);
 
         assert!(result.is_ok());
         let def = result.unwrap();
         if let Definition::Scalar(ScalarDef {
-            name,
-            directives,
-            description: _,
+            name, directives, ..
         }) = def
         {
             assert_eq!(name, "Date");
@@ -51,26 +52,23 @@ mod tests {
         } else {
             panic!("Expected ScalarDef");
         }
-        assert_eq!(index, 2);
+        assert_eq!(parser.position(), 2);
     }
 
     #[test]
     fn test_parse_scalar_with_directive() {
-        let tokens = vec![
-            Token::Name("scalar".to_string()),
-            Token::Name("Date".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("scalar"),
+            Token::Name("Date"),
             Token::At,
-            Token::Name("deprecated".to_string()),
-        ];
-        let mut index = 0;
-        let result = parse_scalar(&tokens, &mut index, None);
+            Token::Name("deprecated"),
+        ]));
+        let result = parse_scalar(&mut parser, None);
 
         assert!(result.is_ok());
         let def = result.unwrap();
         if let Definition::Scalar(ScalarDef {
-            name,
-            directives,
-            description: _,
+            name, directives, ..
         }) = def
         {
             assert_eq!(name, "Date");
@@ -80,31 +78,28 @@ mod tests {
         } else {
             panic!("Expected ScalarDef");
         }
-        assert_eq!(index, 4);
+        assert_eq!(parser.position(), 4);
     }
 
     #[test]
     fn test_parse_scalar_with_directive_arguments() {
-        let tokens = vec![
-            Token::Name("scalar".to_string()),
-            Token::Name("Date".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("scalar"),
+            Token::Name("Date"),
             Token::At,
-            Token::Name("directive".to_string()),
+            Token::Name("directive"),
             Token::ParenOpen,
-            Token::Name("reason".to_string()),
+            Token::Name("reason"),
             Token::Colon,
-            Token::String("legacy".to_string()),
+            Token::String("legacy".into()),
             Token::ParenClose,
-        ];
-        let mut index = 0;
-        let result = parse_scalar(&tokens, &mut index, None);
+        ]));
+        let result = parse_scalar(&mut parser, None);
 
         assert!(result.is_ok());
         let def = result.unwrap();
         if let Definition::Scalar(ScalarDef {
-            name,
-            directives,
-            description: _,
+            name, directives, ..
         }) = def
         {
             assert_eq!(name, "Date");
@@ -114,43 +109,38 @@ mod tests {
 
             let args = directives[0].arguments.as_ref().unwrap();
             assert_eq!(args[0].name, "reason");
-            assert_eq!(
-                format!("{:?}", args[0].default_value),
-                "Some(String(\"legacy\"))"
-            );
+            assert_eq!(format!("{:?}", args[0].value), "String(\"legacy\")");
         } else {
             panic!("Expected ScalarDef");
         }
-        assert_eq!(index, 9);
+        assert_eq!(parser.position(), 9);
     }
 
     #[test]
     fn test_parse_scalar_missing_name() {
-        let tokens = vec![
-            Token::Name("scalar".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("scalar"),
             Token::Colon, // Invalid — colon instead of a name
-        ];
-        let mut index = 0;
-        let result = parse_scalar(&tokens, &mut index, None);
+        ]));
+        let result = parse_scalar(&mut parser, None);
 
         assert!(result.is_err());
         let err = result.unwrap_err().to_string();
-        assert!(err.contains("Expected name"), "Unexpected error: {err}");
+        assert!(err.contains("Expected a name"), "Unexpected error: {err}");
     }
 
     #[test]
     fn test_parse_scalar_unexpected_token() {
-        let tokens = vec![
-            Token::Name("scalar".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("scalar"),
             Token::ParenOpen,
-            Token::Name("Date".to_string()),
-        ];
-        let mut index = 0;
-        let result = parse_scalar(&tokens, &mut index, None);
+            Token::Name("Date"),
+        ]));
+        let result = parse_scalar(&mut parser, None);
 
         assert!(result.is_err(), "Expected Err, got {:?}", result);
         if let Err(err) = result {
-            assert_eq!(err.to_string(), "Expected name, got Some(ParenOpen)");
+            assert_eq!(err.to_string(), "Expected a name, found `(`");
         } else {
             panic!("Expected Err, got {:?}", result);
         }
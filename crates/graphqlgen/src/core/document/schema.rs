@@ -0,0 +1,197 @@
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{
+    Comments, Definition, Directive, OperationType, RootOperationTypeDef, SchemaDef,
+};
+
+use crate::core::common::{
+    parse::directives::parse_const_directives, parser::Parser, token::Token,
+};
+
+pub fn parse_schema(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    parse_schema_def(parser, description, true).map(Definition::Schema)
+}
+
+/// Parses `schema @directives { query: Query }`. Extensions pass
+/// `require_operation_types: false`, as they may add directives only.
+pub fn parse_schema_def(
+    parser: &mut Parser,
+    description: Option<String>,
+    require_operation_types: bool,
+) -> Result<SchemaDef> {
+    let start: usize = parser.position();
+    parser.bump();
+    let directives: Vec<Directive> = parse_const_directives(parser)?;
+    let directives: Option<Vec<Directive>> = if directives.is_empty() {
+        None
+    } else {
+        Some(directives)
+    };
+
+    if !require_operation_types && !parser.at(&Token::BraceOpen) {
+        return Ok(SchemaDef {
+            operation_types: Vec::new(),
+            directives,
+            description,
+            span: parser.span_from(start),
+            comments: Comments::default(),
+        });
+    }
+
+    let brace: usize = parser.position();
+    parser.expect(Token::BraceOpen)?;
+
+    let mut operation_types: Vec<RootOperationTypeDef> = Vec::new();
+
+    while !parser.at(&Token::BraceClose) {
+        let root_start: usize = parser.position();
+        let keyword: String = parser.expect_name()?;
+        let operation: OperationType = OperationType::from_keyword(&keyword).ok_or_else(|| {
+            parser
+                .unexpected_at(
+                    root_start,
+                    format!("Unknown root operation type '{}'", keyword),
+                )
+                .with_help("expected `query`, `mutation` or `subscription`")
+        })?;
+
+        parser.expect(Token::Colon)?;
+        let type_name: String = parser.expect_name()?;
+
+        operation_types.push(RootOperationTypeDef {
+            operation,
+            type_name,
+            span: parser.span_from(root_start),
+            comments: Comments::default(),
+        });
+    }
+
+    if operation_types.is_empty() {
+        return Err(parser.unexpected_at(
+            brace,
+            "Schema definition must declare at least one root operation type",
+        ));
+    }
+    parser.bump();
+
+    Ok(SchemaDef {
+        operation_types,
+        directives,
+        description,
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
+
+    #[test]
+    fn test_parse_schema() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("schema"),
+            Token::BraceOpen,
+            Token::Name("query"),
+            Token::Colon,
+            Token::Name("RootQuery"),
+            Token::Name("mutation"),
+            Token::Colon,
+            Token::Name("RootMutation"),
+            Token::BraceClose,
+        ]));
+
+        let result = parse_schema(&mut parser, None).unwrap();
+        assert_eq!(parser.position(), 9);
+
+        if let Definition::Schema(schema) = result {
+            assert_eq!(schema.operation_types.len(), 2);
+            assert_eq!(schema.operation_types[0].operation, OperationType::Query);
+            assert_eq!(schema.operation_types[0].type_name, "RootQuery");
+            assert_eq!(schema.operation_types[1].operation, OperationType::Mutation);
+            assert_eq!(schema.operation_types[1].type_name, "RootMutation");
+            assert!(schema.directives.is_none());
+        } else {
+            panic!("Expected Definition::Schema");
+        }
+    }
+
+    #[test]
+    fn test_parse_schema_with_directives() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("schema"),
+            Token::At,
+            Token::Name("link"),
+            Token::ParenOpen,
+            Token::Name("url"),
+            Token::Colon,
+            Token::String("https://specs.apollo.dev/federation/v2.3".into()),
+            Token::ParenClose,
+            Token::BraceOpen,
+            Token::Name("subscription"),
+            Token::Colon,
+            Token::Name("Events"),
+            Token::BraceClose,
+        ]));
+
+        let result = parse_schema(&mut parser, None).unwrap();
+
+        if let Definition::Schema(schema) = result {
+            assert_eq!(schema.directives.unwrap()[0].name, "link");
+            assert_eq!(
+                schema.operation_types[0].operation,
+                OperationType::Subscription
+            );
+        } else {
+            panic!("Expected Definition::Schema");
+        }
+    }
+
+    #[test]
+    fn test_parse_schema_unknown_operation() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("schema"),
+            Token::BraceOpen,
+            Token::Name("queries"),
+            Token::Colon,
+            Token::Name("Query"),
+            Token::BraceClose,
+        ]));
+
+        let result = parse_schema(&mut parser, None);
+        assert_eq!(
+            result.unwrap_err().message,
+            "Unknown root operation type 'queries'"
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_empty() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("schema"),
+            Token::BraceOpen,
+            Token::BraceClose,
+        ]));
+
+        let result = parse_schema(&mut parser, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_schema_missing_brace() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("schema"),
+            Token::BraceOpen,
+            Token::Name("query"),
+            Token::Colon,
+            Token::Name("Query"),
+        ]));
+
+        let result = parse_schema(&mut parser, None);
+        assert!(result
+            .unwrap_err()
+            .message
+            .contains("Expected a name, found end of input"));
+    }
+}
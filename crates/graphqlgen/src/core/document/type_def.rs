@@ -1,31 +1,36 @@
-use anyhow::Result;
-use graphqlgen_schema::ast::{Definition, Directive, TypeDef};
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{Comments, Definition, Directive, TypeDef};
 
 use crate::core::common::{
     fields::parse_fields::parse_fields,
-    parse::{
-        directives::parse_directives,
-        expect::{expect_name, expect_token},
-    },
+    parse::{directives::parse_const_directives, implements::parse_implements},
 };
 
-use super::token::Token;
+use super::{parser::Parser, token::Token};
 
-pub fn parse_type(
-    tokens: &[Token],
-    index: &mut usize,
-    description: Option<String>,
-) -> Result<Definition> {
-    *index += 1;
-    let type_name: String = expect_name(&tokens, index)?;
-    let directives: Vec<Directive> = parse_directives(&tokens, index)?;
+pub fn parse_type(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    parse_type_def(parser, description).map(Definition::Type)
+}
 
-    expect_token(&tokens, index, Token::BraceOpen)?;
+/// Parses `type Name implements A & B @directives { fields }`. The fields are
+/// optional, as the spec allows; the validator reports a type left without
+/// any, while extensions may add interfaces or directives only.
+pub fn parse_type_def(parser: &mut Parser, description: Option<String>) -> Result<TypeDef> {
+    let start: usize = parser.position();
+    parser.bump();
+    let type_name: String = parser.expect_name()?;
+    let interfaces: Vec<String> = parse_implements(parser)?;
+    let directives: Vec<Directive> = parse_const_directives(parser)?;
 
-    let fields = parse_fields(&tokens, index)?;
+    let fields = if parser.eat(&Token::BraceOpen) {
+        parse_fields(parser)?
+    } else {
+        Vec::new()
+    };
 
-    Ok(Definition::Type(TypeDef {
+    Ok(TypeDef {
         name: type_name,
+        interfaces,
         fields,
         directives: if directives.is_empty() {
             None
@@ -33,33 +38,34 @@ pub fn parse_type(
             Some(directives)
         },
         description,
-    }))
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use graphqlgen_schema::ast::TypeRef;
+    use graphqlgen_schema::ast::{Field, TypeRef, Value};
 
     use super::*;
+    use crate::core::common::token::spanned;
 
     #[test]
     fn test_parse_type() {
-        let tokens: Vec<Token> = vec![
-            Token::Name("type".to_string()),
-            Token::Name("Address".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("type"),
+            Token::Name("Address"),
             Token::BraceOpen,
-            Token::Name("address".to_string()),
+            Token::Name("address"),
             Token::Colon,
-            Token::Name("String".to_string()),
-            Token::Name("city".to_string()),
+            Token::Name("String"),
+            Token::Name("city"),
             Token::Colon,
-            Token::Name("String".to_string()),
+            Token::Name("String"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index: usize = 0;
-        let result: std::result::Result<Definition, anyhow::Error> =
-            parse_type(&tokens, &mut index, None);
+        let result: Result<Definition> = parse_type(&mut parser, None);
         assert!(result.is_ok(), "Expected Ok, got {:?}", result);
         let definition: Definition = result.unwrap();
 
@@ -84,7 +90,7 @@ mod tests {
                     type_def.fields[1].field_type
                 );
             }
-            assert_eq!(index, 10);
+            assert_eq!(parser.position(), 10);
 
             assert!(type_def.directives.is_none());
         } else {
@@ -94,26 +100,21 @@ mod tests {
 
     #[test]
     fn test_parse_type_invalid() {
-        let tokens: Vec<Token> = vec![
-            Token::Name("type".to_string()),
-            Token::Name("InvalidAddress".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("type"),
+            Token::Name("InvalidAddress"),
             Token::BraceOpen,
-            Token::Name("address".to_string()),
+            Token::Name("address"),
             Token::Colon,
             Token::Colon,
-            Token::Name("String".to_string()),
+            Token::Name("String"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index: usize = 0;
-        let result: std::result::Result<Definition, anyhow::Error> =
-            parse_type(&tokens, &mut index, None);
+        let result: Result<Definition> = parse_type(&mut parser, None);
         assert!(result.is_err(), "Expected Err, got {:?}", result);
         if let Err(err) = result {
-            assert_eq!(
-                err.to_string(),
-                "Unexpected token in type reference: Some(Colon)"
-            );
+            assert_eq!(err.to_string(), "Unexpected `:` in type reference");
         } else {
             panic!("Expected Err, got {:?}", result);
         }
@@ -121,22 +122,20 @@ mod tests {
 
     #[test]
     fn test_parse_type_with_directives() {
-        let tokens: Vec<Token> = vec![
-            Token::Name("type".to_string()),
-            Token::Name("Address".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("type"),
+            Token::Name("Address"),
             Token::BraceOpen,
-            Token::Name("address".to_string()),
+            Token::Name("address"),
             Token::Colon,
-            Token::Name("String".to_string()),
-            Token::Name("city".to_string()),
+            Token::Name("String"),
+            Token::Name("city"),
             Token::Colon,
-            Token::Name("String".to_string()),
+            Token::Name("String"),
             Token::BraceClose,
-        ];
+        ]));
 
-        let mut index: usize = 0;
-        let result: std::result::Result<Definition, anyhow::Error> =
-            parse_type(&tokens, &mut index, None);
+        let result: Result<Definition> = parse_type(&mut parser, None);
         assert!(result.is_ok(), "Expected Ok, got {:?}", result);
         let definition: Definition = result.unwrap();
 
@@ -161,11 +160,165 @@ mod tests {
                     type_def.fields[1].field_type
                 );
             }
-            assert_eq!(index, 10);
+            assert_eq!(parser.position(), 10);
 
             assert!(type_def.directives.is_none());
         } else {
             panic!("Expected TypeDef, got {:?}", definition);
         }
     }
+
+    #[test]
+    fn test_parse_type_spans() {
+        let mut parser = Parser::new("type User @key {\n  id: ID!\n}");
+
+        let definition: Definition = parse_type(&mut parser, None).unwrap();
+
+        if let Definition::Type(type_def) = definition {
+            assert_eq!(
+                (type_def.span.start.line, type_def.span.start.column),
+                (1, 1)
+            );
+            assert_eq!((type_def.span.end.line, type_def.span.end.column), (3, 2));
+
+            let directive = &type_def.directives.as_ref().unwrap()[0];
+            assert_eq!(
+                (directive.span.start.column, directive.span.end.column),
+                (11, 15)
+            );
+
+            let field = &type_def.fields[0];
+            assert_eq!((field.span.start.line, field.span.start.column), (2, 3));
+            assert_eq!((field.span.end.line, field.span.end.column), (2, 10));
+        } else {
+            panic!("Expected TypeDef, got {:?}", definition);
+        }
+    }
+
+    #[test]
+    fn test_parse_field_arguments() {
+        let mut parser = Parser::new(
+            r#"type Query {
+                posts(
+                    """Page size"""
+                    limit: Int = 10 @deprecated
+                    order: Order = DESC
+                    tags: [String!] = ["news"]
+                ): [Post]
+            }"#,
+        );
+
+        let definition: Definition = parse_type(&mut parser, None).unwrap();
+
+        if let Definition::Type(type_def) = definition {
+            let args = type_def.fields[0].arguments.as_ref().unwrap();
+            assert_eq!(args.len(), 3);
+
+            assert_eq!(args[0].description.as_deref(), Some("Page size"));
+            assert_eq!(args[0].default_value, Some(Value::Int(10)));
+            assert_eq!(args[0].directives.as_ref().unwrap()[0].name, "deprecated");
+            assert_eq!(args[1].default_value, Some(Value::Enum("DESC".into())));
+            assert_eq!(
+                args[2].default_value,
+                Some(Value::List(vec![Value::String("news".into())]))
+            );
+        } else {
+            panic!("Expected TypeDef, got {:?}", definition);
+        }
+    }
+
+    #[test]
+    fn test_parse_value_keywords_as_names() {
+        let mut parser = Parser::new("type Query { true: Boolean null(false: Int): Int }");
+
+        let definition: Definition = parse_type(&mut parser, None).unwrap();
+
+        if let Definition::Type(type_def) = definition {
+            assert_eq!(type_def.fields[0].name, "true");
+            assert_eq!(type_def.fields[1].name, "null");
+            assert_eq!(
+                type_def.fields[1].arguments.as_ref().unwrap()[0].name,
+                "false"
+            );
+        } else {
+            panic!("Expected TypeDef, got {:?}", definition);
+        }
+    }
+
+    #[test]
+    fn test_parse_type_implements() {
+        let mut parser = Parser::new("type User implements Node & Entity @key { id: ID! }");
+
+        let definition: Definition = parse_type(&mut parser, None).unwrap();
+
+        if let Definition::Type(type_def) = definition {
+            assert_eq!(type_def.interfaces, vec!["Node", "Entity"]);
+            assert_eq!(type_def.directives.unwrap()[0].name, "key");
+            assert_eq!(type_def.fields.len(), 1);
+        } else {
+            panic!("Expected TypeDef, got {:?}", definition);
+        }
+    }
+
+    #[test]
+    fn test_parse_deprecations() {
+        let mut parser = Parser::new(
+            r#"type User {
+                name: String @deprecated(reason: "Use `fullName`.")
+                age(unit: Unit @deprecated): Int @deprecated
+                email: String @tag(reason: "not deprecated")
+            }"#,
+        );
+
+        let definition: Definition = parse_type(&mut parser, None).unwrap();
+
+        if let Definition::Type(type_def) = definition {
+            let reason = |field: &Field| field.deprecation.as_ref().map(|d| d.reason.clone());
+            assert_eq!(
+                reason(&type_def.fields[0]).as_deref(),
+                Some("Use `fullName`.")
+            );
+            assert_eq!(
+                reason(&type_def.fields[1]).as_deref(),
+                Some("No longer supported")
+            );
+            assert_eq!(reason(&type_def.fields[2]), None);
+
+            let unit = &type_def.fields[1].arguments.as_ref().unwrap()[0];
+            assert_eq!(
+                unit.deprecation.as_ref().unwrap().reason,
+                "No longer supported"
+            );
+        } else {
+            panic!("Expected TypeDef, got {:?}", definition);
+        }
+    }
+
+    #[test]
+    fn test_parse_string_descriptions() {
+        let mut parser = Parser::new(
+            r#"type User {
+                "User id"
+                id: ID!
+                "Posts, newest first"
+                posts("Page size" first: Int, after: String): [Post]
+            }"#,
+        );
+
+        let definition: Definition = parse_type(&mut parser, None).unwrap();
+
+        if let Definition::Type(type_def) = definition {
+            assert_eq!(type_def.fields[0].description.as_deref(), Some("User id"));
+            assert_eq!(
+                type_def.fields[1].description.as_deref(),
+                Some("Posts, newest first")
+            );
+
+            let args = type_def.fields[1].arguments.as_ref().unwrap();
+            assert_eq!(args[0].description.as_deref(), Some("Page size"));
+            assert_eq!(args[1].description, None);
+        } else {
+            panic!("Expected TypeDef, got {:?}", definition);
+        }
+    }
 }
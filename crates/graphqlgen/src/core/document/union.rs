@@ -1,66 +1,116 @@
-use crate::core::common::parse::expect::{expect_name, expect_token};
+use super::{parser::Parser, token::Token};
 
-use super::token::Token;
+use crate::core::common::error::Result;
+use crate::core::common::parse::{
+    directives::parse_const_directives,
+    expect::{describe_found, expected_here},
+    recover::starts_definition,
+};
+use graphqlgen_schema::ast::{Comments, Definition, Directive, TypeRef, UnionDef};
 
-use anyhow::Result;
-use graphqlgen_schema::ast::{Definition, TypeRef, UnionDef};
+pub fn parse_union(parser: &mut Parser, description: Option<String>) -> Result<Definition> {
+    parse_union_def(parser, description, true).map(Definition::Union)
+}
 
-pub fn parse_union(
-    tokens: &[Token],
-    index: &mut usize,
+/// Parses `union Name @directives = A | B`, with an optional `|` before the
+/// first member. Extensions pass `require_members: false`, as they may add
+/// directives only.
+pub fn parse_union_def(
+    parser: &mut Parser,
     description: Option<String>,
-) -> Result<Definition> {
-    *index += 1;
-
-    let name = expect_name(tokens, index)?;
-    expect_token(tokens, index, Token::Equals)?;
-
-    let mut members = Vec::new();
-
-    loop {
-        match tokens.get(*index) {
-            Some(Token::Name(member)) => {
-                members.push(TypeRef::Named(member.clone()));
-                *index += 1;
-            }
-            Some(Token::Pipe) => {
-                *index += 1;
-            }
-            _ => break,
-        }
+    require_members: bool,
+) -> Result<UnionDef> {
+    let start: usize = parser.position();
+    parser.bump();
+
+    let name: String = parser.expect_name()?;
+    let directives: Vec<Directive> = parse_const_directives(parser)?;
+    let directives: Option<Vec<Directive>> = if directives.is_empty() {
+        None
+    } else {
+        Some(directives)
+    };
+
+    if !require_members && !parser.at(&Token::Equals) {
+        return Ok(UnionDef {
+            name,
+            members: Vec::new(),
+            description,
+            directives,
+            span: parser.span_from(start),
+            comments: Comments::default(),
+        });
     }
+    parser.expect(Token::Equals)?;
+    parser.eat(&Token::Pipe);
 
-    Ok(Definition::Union(UnionDef {
+    let mut members: Vec<TypeRef> = vec![parse_member(parser, &name, true)?];
+    while parser.eat(&Token::Pipe) {
+        members.push(parse_member(parser, &name, false)?);
+    }
+
+    Ok(UnionDef {
         name,
         members,
         description,
-        directives: None,
-    }))
+        directives,
+        span: parser.span_from(start),
+        comments: Comments::default(),
+    })
+}
+
+/// Parses one member type. A definition keyword followed by a name, as in
+/// `union U = type A`, starts the next definition rather than naming a member.
+fn parse_member(parser: &mut Parser, union_name: &str, first: bool) -> Result<TypeRef> {
+    if matches!(parser.peek(), Some(Token::Name(_))) && !starts_definition(parser, 0) {
+        return Ok(TypeRef::Named(parser.expect_name()?));
+    }
+
+    let diagnostic = if first {
+        parser
+            .unexpected(format!(
+                "Union '{}' must have at least one member",
+                union_name
+            ))
+            .with_help(format!(
+                "list the member types, e.g. `union {} = A | B`",
+                union_name
+            ))
+    } else {
+        parser
+            .unexpected(format!(
+                "Expected a member type after `|`, found {}",
+                describe_found(parser.peek())
+            ))
+            .with_help("remove the trailing `|`")
+    };
+    Err(expected_here(diagnostic, "a member type"))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::common::token::spanned;
     use graphqlgen_schema::ast::{Definition, TypeRef, UnionDef};
 
     #[test]
     fn test_parse_union_single_member() {
-        let tokens = vec![
-            Token::Name("union".to_string()),
-            Token::Name("SearchResult".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
+            Token::Name("SearchResult"),
             Token::Equals,
-            Token::Name("Photo".to_string()),
-        ];
+            Token::Name("Photo"),
+        ]));
 
-        let mut index = 0;
-        let result = parse_union(&tokens, &mut index, None);
+        let result = parse_union(&mut parser, None);
         assert!(result.is_ok());
 
         if let Definition::Union(UnionDef {
             name,
             members,
             directives,
-            description: _,
+            ..
         }) = result.unwrap()
         {
             assert_eq!(name, "SearchResult");
@@ -73,148 +123,201 @@ mod tests {
 
     #[test]
     fn test_parse_union_multiple_members() {
-        let tokens = vec![
-            Token::Name("union".to_string()),
-            Token::Name("SearchResult".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
+            Token::Name("SearchResult"),
             Token::Equals,
-            Token::Name("Photo".to_string()),
+            Token::Name("Photo"),
             Token::Pipe,
-            Token::Name("User".to_string()),
+            Token::Name("User"),
             Token::Pipe,
-            Token::Name("Comment".to_string()),
-        ];
+            Token::Name("Comment"),
+        ]));
 
-        let mut index = 0;
-        let result = parse_union(&tokens, &mut index, None);
+        let result = parse_union(&mut parser, None);
         assert!(result.is_ok());
 
         if let Definition::Union(UnionDef { name, members, .. }) = result.unwrap() {
             assert_eq!(name, "SearchResult");
             assert!(members.len() == 3);
-            assert!(matches!(members[0], TypeRef::Named(ref name) if name == "Photo"));
-            assert!(matches!(members[1], TypeRef::Named(ref name) if name == "User"));
-            assert!(matches!(members[2], TypeRef::Named(ref name) if name == "Comment"));
+            assert!(matches!(members[0], TypeRef::Named(ref name) if *name == "Photo"));
+            assert!(matches!(members[1], TypeRef::Named(ref name) if *name == "User"));
+            assert!(matches!(members[2], TypeRef::Named(ref name) if *name == "Comment"));
         }
     }
 
     #[test]
     fn test_parse_union_pipe_at_start() {
-        let tokens = vec![
-            Token::Name("union".to_string()),
-            Token::Name("Result".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
+            Token::Name("Result"),
             Token::Equals,
             Token::Pipe,
-            Token::Name("A".to_string()),
+            Token::Name("A"),
             Token::Pipe,
-            Token::Name("B".to_string()),
-        ];
+            Token::Name("B"),
+        ]));
 
-        let mut index = 0;
-        let result = parse_union(&tokens, &mut index, None);
+        let result = parse_union(&mut parser, None);
         assert!(result.is_ok());
 
         if let Definition::Union(UnionDef { name, members, .. }) = result.unwrap() {
             assert_eq!(name, "Result");
             assert_eq!(members.len(), 2);
-            assert!(matches!(members[0], TypeRef::Named(ref name) if name == "A"));
-            assert!(matches!(members[1], TypeRef::Named(ref name) if name == "B"));
+            assert!(matches!(members[0], TypeRef::Named(ref name) if *name == "A"));
+            assert!(matches!(members[1], TypeRef::Named(ref name) if *name == "B"));
         }
     }
 
     #[test]
     fn test_parse_union_missing_name() {
-        let tokens = vec![
-            Token::Name("union".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
             Token::Equals,
-            Token::Name("Something".to_string()),
-        ];
+            Token::Name("Something"),
+        ]));
 
-        let mut index = 0;
-        let result = parse_union(&tokens, &mut index, None);
+        let result = parse_union(&mut parser, None);
         assert!(result.is_err());
         let msg = result.unwrap_err().to_string();
-        assert!(msg.contains("Expected name"));
+        assert!(msg.contains("Expected a name"));
     }
 
     #[test]
     fn test_parse_union_missing_equals() {
-        let tokens = vec![
-            Token::Name("union".to_string()),
-            Token::Name("Result".to_string()),
-            Token::Name("Photo".to_string()),
-        ];
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
+            Token::Name("Result"),
+            Token::Name("Photo"),
+        ]));
 
-        let mut index = 0;
-        let result = parse_union(&tokens, &mut index, None);
+        let result = parse_union(&mut parser, None);
 
         assert!(result.is_err());
         assert!(result
             .unwrap_err()
             .to_string()
-            .contains("Expected Equals, got Some(Name(\"Photo\"))"));
+            .contains("Expected `=`, found name `Photo`"));
     }
 
     #[test]
     fn test_parse_union_no_members() {
-        let tokens = vec![
-            Token::Name("union".to_string()),
-            Token::Name("EmptyUnion".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
+            Token::Name("EmptyUnion"),
             Token::Equals,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_union(&tokens, &mut index, None);
-        assert!(result.is_ok());
+        let err = parse_union(&mut parser, None).unwrap_err();
+        assert_eq!(err.code, "unexpected-eof");
+        assert_eq!(
+            err.message,
+            "Union 'EmptyUnion' must have at least one member"
+        );
+    }
 
-        if let Definition::Union(UnionDef { members, .. }) = result.unwrap() {
-            assert!(
-                members.is_empty(),
-                "Expected empty members, got {:?}",
-                members
-            );
-        }
+    #[test]
+    fn test_parse_union_only_leading_pipe() {
+        let err = parse_union(&mut Parser::new("union U = |"), None).unwrap_err();
+        assert_eq!(err.message, "Union 'U' must have at least one member");
     }
 
     #[test]
     fn test_parse_union_invalid_token_in_members() {
-        let tokens = vec![
-            Token::Name("union".to_string()),
-            Token::Name("Weird".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
+            Token::Name("Weird"),
             Token::Equals,
             Token::Colon, // Invalid token here
-            Token::Name("X".to_string()),
-        ];
+            Token::Name("X"),
+        ]));
 
-        let mut index = 0;
-        let result = parse_union(&tokens, &mut index, None);
-        assert!(result.is_ok());
+        let err = parse_union(&mut parser, None).unwrap_err();
+        assert_eq!(err.code, "unexpected-token");
+        assert_eq!(err.message, "Union 'Weird' must have at least one member");
+        assert_eq!(parser.position(), 3);
+    }
 
-        if let Definition::Union(UnionDef { members, .. }) = result.unwrap() {
-            assert!(
-                members.is_empty(),
-                "Unexpectedly parsed members: {:?}",
-                members
-            );
-            assert_eq!(index, 3); // parsing should stop at unexpected token
+    #[test]
+    fn test_parse_union_stops_at_next_definition() {
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
+            Token::Name("Media"),
+            Token::Equals,
+            Token::Name("Photo"),
+            Token::Name("type"),
+            Token::Name("Photo"),
+        ]));
+
+        let result = parse_union(&mut parser, None).unwrap();
+        assert_eq!(parser.position(), 4);
+
+        if let Definition::Union(UnionDef { members, .. }) = result {
+            assert_eq!(members.len(), 1);
         }
     }
 
+    #[test]
+    fn test_parse_union_does_not_take_next_definition_as_member() {
+        let err = parse_union(&mut Parser::new("union U = type A { id: ID }"), None).unwrap_err();
+        assert_eq!(err.message, "Union 'U' must have at least one member");
+        assert_eq!(err.span().unwrap().start.column, 11);
+    }
+
     #[test]
     fn test_parse_union_trailing_pipe() {
-        let tokens = vec![
-            Token::Name("union".to_string()),
-            Token::Name("Broken".to_string()),
+        let mut parser = Parser::from_tokens(spanned(vec![
+            Token::Name("union"),
+            Token::Name("Broken"),
             Token::Equals,
-            Token::Name("X".to_string()),
+            Token::Name("X"),
             Token::Pipe,
-        ];
+        ]));
 
-        let mut index = 0;
-        let result = parse_union(&tokens, &mut index, None);
-        assert!(result.is_ok());
+        let err = parse_union(&mut parser, None).unwrap_err();
+        assert_eq!(
+            err.message,
+            "Expected a member type after `|`, found end of input"
+        );
+        assert_eq!(err.help.as_deref(), Some("remove the trailing `|`"));
+    }
+
+    #[test]
+    fn test_parse_union_pipe_followed_by_invalid_token() {
+        let err = parse_union(&mut Parser::new("union U = A | [B]"), None).unwrap_err();
+        assert_eq!(err.message, "Expected a member type after `|`, found `[`");
+    }
+
+    #[test]
+    fn test_parse_union_with_directives() {
+        let mut parser = Parser::new(r#"union U @tag(name: "x") @key = | A | B"#);
+
+        let definition: Definition = parse_union(&mut parser, None).unwrap();
+        assert!(parser.at_end());
 
-        if let Definition::Union(UnionDef { members, .. }) = result.unwrap() {
-            assert_eq!(members.len(), 1); // Only X should be collected
+        if let Definition::Union(UnionDef {
+            members,
+            directives,
+            ..
+        }) = definition
+        {
+            let directives = directives.unwrap();
+            assert_eq!(directives.len(), 2);
+            assert_eq!(directives[0].name, "tag");
+            assert_eq!(directives[1].name, "key");
+            assert_eq!(members.len(), 2);
+        } else {
+            panic!("Expected UnionDef");
         }
     }
+
+    #[test]
+    fn test_parse_union_extension_with_directives_only() {
+        let mut parser = Parser::new("union U @tag type T { id: ID }");
+
+        let union_def: UnionDef = parse_union_def(&mut parser, None, false).unwrap();
+        assert!(union_def.members.is_empty());
+        assert_eq!(union_def.directives.unwrap()[0].name, "tag");
+        assert!(parser.at_keyword("type"));
+    }
 }
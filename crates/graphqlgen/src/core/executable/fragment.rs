@@ -0,0 +1,90 @@
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{ExecutableDefinition, FragmentDef};
+
+use crate::core::common::{
+    parse::{
+        directives::parse_directives,
+        expect::{describe_found, expected_here},
+    },
+    parser::Parser,
+};
+
+use super::selection_set::parse_selection_set;
+
+/// Parses `fragment Name on Type @directives { ... }`.
+pub fn parse_fragment(parser: &mut Parser) -> Result<ExecutableDefinition> {
+    let start: usize = parser.position();
+    parser.bump();
+
+    let name_index: usize = parser.position();
+    let name: String = parser.expect_name()?;
+    if name == "on" {
+        return Err(parser.unexpected_at(name_index, "A fragment cannot be named 'on'"));
+    }
+
+    if !parser.eat_keyword("on") {
+        return Err(expected_here(
+            parser.unexpected(format!(
+                "Expected `on`, found {}",
+                describe_found(parser.peek())
+            )),
+            "`on` followed by the type the fragment applies to",
+        ));
+    }
+
+    let type_condition: String = parser.expect_name()?;
+    let directives = parse_directives(parser)?;
+    let selection_set = parse_selection_set(parser)?;
+
+    Ok(ExecutableDefinition::Fragment(FragmentDef {
+        name,
+        type_condition,
+        directives: if directives.is_empty() {
+            None
+        } else {
+            Some(directives)
+        },
+        selection_set,
+        span: parser.span_from(start),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+
+    fn parse(source: &str) -> Result<ExecutableDefinition> {
+        let mut parser = Parser::new(source);
+        parse_fragment(&mut parser)
+    }
+
+    #[test]
+    fn test_parse_fragment() {
+        let result = parse("fragment UserFields on User @public { id name }").unwrap();
+
+        if let ExecutableDefinition::Fragment(fragment) = result {
+            assert_eq!(fragment.name, "UserFields");
+            assert_eq!(fragment.type_condition, "User");
+            assert_eq!(fragment.directives.unwrap()[0].name, "public");
+            assert_eq!(fragment.selection_set.len(), 2);
+        } else {
+            panic!("Expected ExecutableDefinition::Fragment");
+        }
+    }
+
+    #[test]
+    fn test_parse_fragment_missing_type_condition() {
+        let result = parse("fragment UserFields { id }");
+        assert_eq!(result.unwrap_err().message, "Expected `on`, found `{`");
+    }
+
+    #[test]
+    fn test_parse_fragment_named_on() {
+        let result = parse("fragment on on User { id }");
+        assert_eq!(
+            result.unwrap_err().message,
+            "A fragment cannot be named 'on'"
+        );
+    }
+}
@@ -0,0 +1,3 @@
+pub mod fragment;
+pub mod operation;
+pub mod selection_set;
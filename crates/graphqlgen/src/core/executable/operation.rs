@@ -0,0 +1,190 @@
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{ExecutableDefinition, OperationDef, OperationType, VariableDef};
+
+use crate::core::common::{
+    parse::{
+        directives::{parse_const_directives, parse_directives},
+        type_ref::parse_type_ref,
+        value::parse_const_value,
+    },
+    parser::Parser,
+    token::Token,
+};
+
+use super::selection_set::parse_selection_set;
+
+/// Parses `query Name($var: Type) @directives { ... }`, or the `{ ... }`
+/// query shorthand.
+pub fn parse_operation(parser: &mut Parser) -> Result<ExecutableDefinition> {
+    let start: usize = parser.position();
+
+    if parser.at(&Token::BraceOpen) {
+        let selection_set = parse_selection_set(parser)?;
+
+        return Ok(ExecutableDefinition::Operation(OperationDef {
+            operation: OperationType::Query,
+            name: None,
+            variables: Vec::new(),
+            directives: None,
+            selection_set,
+            span: parser.span_from(start),
+        }));
+    }
+
+    let keyword: String = parser.expect_name()?;
+    let operation: OperationType = OperationType::from_keyword(&keyword).ok_or_else(|| {
+        parser
+            .unexpected_at(start, format!("Unknown operation type '{}'", keyword))
+            .with_help("expected `query`, `mutation` or `subscription`")
+    })?;
+
+    let name: Option<String> = match parser.peek() {
+        Some(Token::Name(_)) => Some(parser.expect_name()?),
+        _ => None,
+    };
+
+    let variables: Vec<VariableDef> = if parser.at(&Token::ParenOpen) {
+        parse_variable_definitions(parser)?
+    } else {
+        Vec::new()
+    };
+    let directives = parse_directives(parser)?;
+    let selection_set = parse_selection_set(parser)?;
+
+    Ok(ExecutableDefinition::Operation(OperationDef {
+        operation,
+        name,
+        variables,
+        directives: if directives.is_empty() {
+            None
+        } else {
+            Some(directives)
+        },
+        selection_set,
+        span: parser.span_from(start),
+    }))
+}
+
+/// Parses `($name: Type = default @directives, ...)` with the parser on the
+/// opening paren.
+fn parse_variable_definitions(parser: &mut Parser) -> Result<Vec<VariableDef>> {
+    let open: usize = parser.position();
+    parser.expect(Token::ParenOpen)?;
+
+    let mut variables: Vec<VariableDef> = Vec::new();
+    while !parser.at_end() && !parser.at(&Token::ParenClose) {
+        let start: usize = parser.position();
+        parser.expect(Token::Dollar)?;
+        let name: String = parser.expect_name()?;
+        parser.expect(Token::Colon)?;
+        let var_type = parse_type_ref(parser)?;
+
+        let default_value = if parser.eat(&Token::Equals) {
+            Some(parse_const_value(parser)?)
+        } else {
+            None
+        };
+        let directives = parse_const_directives(parser)?;
+
+        variables.push(VariableDef {
+            name,
+            var_type,
+            default_value,
+            directives: if directives.is_empty() {
+                None
+            } else {
+                Some(directives)
+            },
+            span: parser.span_from(start),
+        });
+    }
+
+    if variables.is_empty() {
+        return Err(parser
+            .unexpected("Expected a variable definition")
+            .with_secondary(parser.span_at(open), "variable list opened here")
+            .with_help("remove the empty `()`"));
+    }
+    parser.expect(Token::ParenClose)?;
+
+    Ok(variables)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use graphqlgen_schema::ast::{Selection, TypeRef, Value};
+
+    fn parse(source: &str) -> Result<OperationDef> {
+        let mut parser = Parser::new(source);
+        match parse_operation(&mut parser)? {
+            ExecutableDefinition::Operation(operation) => Ok(operation),
+            other => panic!("Expected an operation, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_query_with_variables() {
+        let operation = parse(
+            "query GetUser($id: ID!, $first: Int = 10 @deprecated) { user(id: $id) { ...UserFields } }",
+        )
+        .unwrap();
+
+        assert_eq!(operation.operation, OperationType::Query);
+        assert_eq!(operation.name.as_deref(), Some("GetUser"));
+        assert_eq!(operation.variables.len(), 2);
+
+        assert_eq!(operation.variables[0].name, "id");
+        assert!(matches!(
+            operation.variables[0].var_type,
+            TypeRef::NonNull(_)
+        ));
+        assert_eq!(operation.variables[1].default_value, Some(Value::Int(10)));
+        assert!(operation.variables[1].directives.is_some());
+
+        let Selection::Field(user) = &operation.selection_set[0] else {
+            panic!("Expected a field");
+        };
+        assert!(matches!(
+            user.selection_set[0],
+            Selection::FragmentSpread(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_anonymous_operations() {
+        let shorthand = parse("{ me { id } }").unwrap();
+        assert_eq!(shorthand.operation, OperationType::Query);
+        assert!(shorthand.name.is_none());
+
+        let mutation = parse("mutation @atomic { logout }").unwrap();
+        assert_eq!(mutation.operation, OperationType::Mutation);
+        assert!(mutation.name.is_none());
+        assert_eq!(mutation.directives.unwrap()[0].name, "atomic");
+    }
+
+    #[test]
+    fn test_parse_variable_without_dollar() {
+        let result = parse("query Q(id: ID) { a }");
+        assert_eq!(result.unwrap_err().message, "Expected `$`, found name `id`");
+    }
+
+    #[test]
+    fn test_parse_variable_default_referencing_variable() {
+        let result = parse("query Q($a: Int, $b: Int = $a) { f(a: $a) }");
+        assert_eq!(
+            result.unwrap_err().message,
+            "Variables are not allowed in constant values"
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_operation_type() {
+        let result = parse("queries Q { a }");
+        assert_eq!(
+            result.unwrap_err().message,
+            "Unknown operation type 'queries'"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use crate::core::common::error::Result;
+use graphqlgen_schema::ast::{FieldSelection, FragmentSpread, InlineFragment, Selection};
+
+use crate::core::common::{
+    parse::{arguments::parse_arguments, directives::parse_directives, expect::describe_found},
+    parser::Parser,
+    token::Token,
+};
+
+/// Parses `{ selection ... }` with the parser on the opening brace. A
+/// selection set must select at least one field or fragment.
+pub fn parse_selection_set(parser: &mut Parser) -> Result<Vec<Selection>> {
+    let open: usize = parser.position();
+    parser.expect(Token::BraceOpen)?;
+
+    let mut selections: Vec<Selection> = Vec::new();
+    loop {
+        match parser.peek() {
+            Some(Token::BraceClose) if !selections.is_empty() => {
+                parser.bump();
+                return Ok(selections);
+            }
+            Some(Token::BraceClose) => {
+                return Err(parser
+                    .unexpected("Selection set must select at least one field")
+                    .with_secondary(parser.span_at(open), "selection set opened here"))
+            }
+            Some(Token::Ellipsis) => selections.push(parse_fragment_selection(parser)?),
+            Some(Token::Name(_) | Token::Boolean(_) | Token::Null) => {
+                selections.push(Selection::Field(parse_field(parser)?))
+            }
+            other => {
+                return Err(parser
+                    .unexpected(format!(
+                        "Expected a field or fragment, found {}",
+                        describe_found(other)
+                    ))
+                    .with_secondary(parser.span_at(open), "selection set opened here"))
+            }
+        }
+    }
+}
+
+fn parse_field(parser: &mut Parser) -> Result<FieldSelection> {
+    let start: usize = parser.position();
+    let mut alias: Option<String> = None;
+    let mut name: String = parser.expect_field_name()?;
+
+    if parser.eat(&Token::Colon) {
+        alias = Some(name);
+        name = parser.expect_field_name()?;
+    }
+
+    let arguments = if parser.at(&Token::ParenOpen) {
+        Some(parse_arguments(parser)?)
+    } else {
+        None
+    };
+    let directives = parse_directives(parser)?;
+
+    let selection_set: Vec<Selection> = if parser.at(&Token::BraceOpen) {
+        parse_selection_set(parser)?
+    } else {
+        Vec::new()
+    };
+
+    Ok(FieldSelection {
+        alias,
+        name,
+        arguments,
+        directives: if directives.is_empty() {
+            None
+        } else {
+            Some(directives)
+        },
+        selection_set,
+        span: parser.span_from(start),
+    })
+}
+
+/// Parses `...Name` or `... on Type { ... }` with the parser on the `...`.
+fn parse_fragment_selection(parser: &mut Parser) -> Result<Selection> {
+    let start: usize = parser.position();
+    parser.bump();
+
+    if matches!(parser.peek(), Some(Token::Name(_))) && !parser.at_keyword("on") {
+        let fragment_name: String = parser.expect_name()?;
+        let directives = parse_directives(parser)?;
+
+        return Ok(Selection::FragmentSpread(FragmentSpread {
+            fragment_name,
+            directives: if directives.is_empty() {
+                None
+            } else {
+                Some(directives)
+            },
+            span: parser.span_from(start),
+        }));
+    }
+
+    let type_condition: Option<String> = if parser.eat_keyword("on") {
+        Some(parser.expect_name()?)
+    } else {
+        None
+    };
+    let directives = parse_directives(parser)?;
+    let selection_set: Vec<Selection> = parse_selection_set(parser)?;
+
+    Ok(Selection::InlineFragment(InlineFragment {
+        type_condition,
+        directives: if directives.is_empty() {
+            None
+        } else {
+            Some(directives)
+        },
+        selection_set,
+        span: parser.span_from(start),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use graphqlgen_schema::ast::Value;
+
+    fn parse(source: &str) -> Result<Vec<Selection>> {
+        let mut parser = Parser::new(source);
+        let result = parse_selection_set(&mut parser);
+        if result.is_ok() {
+            assert!(parser.at_end());
+        }
+        result
+    }
+
+    #[test]
+    fn test_parse_fields_with_alias_and_arguments() {
+        let selections =
+            parse("{ me: user(id: $id, first: 10) @include(if: $on) { id name } }").unwrap();
+
+        let Selection::Field(field) = &selections[0] else {
+            panic!("Expected a field, got {:?}", selections[0]);
+        };
+        assert_eq!(field.alias.as_deref(), Some("me"));
+        assert_eq!(field.name, "user");
+        assert_eq!(field.response_key(), "me");
+
+        let arguments = field.arguments.as_ref().unwrap();
+        assert_eq!(arguments[0].value, Value::Variable("id".into()));
+        assert_eq!(arguments[1].value, Value::Int(10));
+        assert_eq!(field.directives.as_ref().unwrap()[0].name, "include");
+        assert_eq!(field.selection_set.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_fragments() {
+        let selections =
+            parse("{ ...UserFields @skip(if: $guest) ... on Admin { level } ... @defer { id } }")
+                .unwrap();
+        assert_eq!(selections.len(), 3);
+
+        match &selections[0] {
+            Selection::FragmentSpread(spread) => {
+                assert_eq!(spread.fragment_name, "UserFields");
+                assert!(spread.directives.is_some());
+            }
+            other => panic!("Expected a fragment spread, got {:?}", other),
+        }
+        match &selections[1] {
+            Selection::InlineFragment(fragment) => {
+                assert_eq!(fragment.type_condition.as_deref(), Some("Admin"));
+            }
+            other => panic!("Expected an inline fragment, got {:?}", other),
+        }
+        match &selections[2] {
+            Selection::InlineFragment(fragment) => {
+                assert!(fragment.type_condition.is_none());
+                assert_eq!(fragment.directives.as_ref().unwrap()[0].name, "defer");
+            }
+            other => panic!("Expected an inline fragment, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_empty_selection_set() {
+        let result = parse("{ user { } }");
+        let error = result.unwrap_err();
+        assert_eq!(
+            error.message,
+            "Selection set must select at least one field"
+        );
+        assert_eq!(error.secondary[0].span.start.column, 8);
+    }
+
+    #[test]
+    fn test_parse_unclosed_selection_set() {
+        let result = parse("{ user { id }");
+        assert_eq!(result.unwrap_err().code, "unexpected-eof");
+    }
+}
@@ -0,0 +1,141 @@
+use graphqlgen_schema::ast::{Definition, Document};
+use graphqlgen_schema::printer::print_document;
+
+use crate::core::common::error::Diagnostic;
+use crate::core::common::parser::Parser;
+use crate::core::parse::parse_document;
+
+/// Formats a schema in the canonical style of
+/// [`graphqlgen_schema::printer`], keeping its `#` comments next to the
+/// definitions, fields and values they belong to. Fails with every error if
+/// the schema does not parse.
+///
+/// The locations of directive definitions are put in the order of the spec.
+/// Applied directives keep their source order, which the spec allows to be
+/// significant.
+pub fn format_schema(source: &str) -> Result<String, Vec<Diagnostic>> {
+    let mut document: Document =
+        parse_document(&mut Parser::with_comments(source)).into_result()?;
+    order_directive_locations(&mut document);
+    Ok(print_document(&document))
+}
+
+fn order_directive_locations(document: &mut Document) {
+    for definition in &mut document.definitions {
+        if let Definition::Directive(def) = definition {
+            def.locations.sort();
+        }
+    }
+}
+
+/// Returns `true` if `source` is already formatted, as `graphqlgen fmt
+/// --check` requires.
+pub fn is_formatted(source: &str) -> Result<bool, Vec<Diagnostic>> {
+    format_schema(source).map(|formatted| formatted == source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(source: &str) -> String {
+        format_schema(source).unwrap()
+    }
+
+    #[test]
+    fn test_formats_canonically() {
+        assert_eq!(
+            format("type User@key(fields:\"id\"){id:ID!,\n\n\n   name:String}\nscalar   Date"),
+            "type User @key(fields: \"id\") {\n  id: ID!\n  name: String\n}\n\nscalar Date\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_comments_around_definitions() {
+        let source: &str = "# License header\n\n# Users\n\"\"\"A user\"\"\"\ntype User {   id: ID! } # trailing\nscalar Date\n\n\n# the end\n";
+
+        assert_eq!(
+            format(source),
+            "# License header\n\n# Users\n\"\"\"A user\"\"\"\ntype User {\n  id: ID!\n} # trailing\n\nscalar Date\n\n# the end\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_comments_inside_definitions() {
+        let source: &str = "enum   Role {\n  ADMIN # full access\n\n  # read only\n  USER\n#  later   \n}\nscalar    Date\n  # UTC";
+
+        assert_eq!(
+            format(source),
+            "enum Role {\n  ADMIN # full access\n  # read only\n  USER\n  #  later\n}\n\nscalar Date\n\n# UTC\n"
+        );
+    }
+
+    #[test]
+    fn test_keeps_comments_on_arguments() {
+        let source: &str =
+            "type Query {\n  posts(first: Int # page size\n  after: String): [Post]\n}\n";
+
+        assert_eq!(
+            format(source),
+            "type Query {\n  posts(\n    first: Int # page size\n    after: String\n  ): [Post]\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_hash_in_strings_is_not_a_comment() {
+        let source: &str = "\"# not a comment\" scalar A @tag(name: \"#x\") # comment";
+
+        assert_eq!(
+            format(source),
+            "\"\"\"# not a comment\"\"\"\nscalar A @tag(name: \"#x\") # comment\n"
+        );
+    }
+
+    #[test]
+    fn test_orders_directive_locations() {
+        let source: &str =
+            "directive @auth(role: String) on FIELD_DEFINITION | OBJECT | QUERY\ntype A @cache @auth { a: Int @auth @cache }";
+
+        assert_eq!(
+            format(source),
+            "directive @auth(role: String) on QUERY | OBJECT | FIELD_DEFINITION\n\ntype A @cache @auth {\n  a: Int @auth @cache\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_description_ending_in_backslash_reparses() {
+        let formatted: String = format("\"C:\\\\\" scalar A");
+
+        assert_eq!(formatted, "\"\"\"\nC:\\\n\"\"\"\nscalar A\n");
+        assert_eq!(format(&formatted), formatted);
+    }
+
+    #[test]
+    fn test_formatting_is_idempotent() {
+        let source: &str = "# header\ntype A{a:Int} # a\n# b\nenum B { X # x\n Y }\n# end";
+        let formatted: String = format(source);
+
+        assert_eq!(format(&formatted), formatted);
+        assert!(is_formatted(&formatted).unwrap());
+        assert!(!is_formatted(source).unwrap());
+
+        let source: &str = "type A {}\nenum E {}\ninput I {}\ninterface N {}";
+        let formatted: String = format(source);
+        assert_eq!(formatted, "type A\n\nenum E\n\ninput I\n\ninterface N\n");
+
+        assert_eq!(format(&formatted), formatted);
+        assert!(is_formatted(&formatted).unwrap());
+        assert!(!is_formatted(source).unwrap());
+    }
+
+    #[test]
+    fn test_reports_parse_errors() {
+        let errors: Vec<Diagnostic> = format_schema("type A { id: }\nscalar §").unwrap_err();
+        assert!(errors
+            .iter()
+            .any(|e| e.message == "Unexpected character: '§'"));
+
+        let errors: Vec<Diagnostic> = format_schema("type A { id: }").unwrap_err();
+        assert_eq!(errors.len(), 1);
+    }
+}
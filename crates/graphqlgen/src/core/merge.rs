@@ -0,0 +1,414 @@
+use graphqlgen_schema::ast::{
+    Comments, Definition, Directive, Document, Extension, InputObjectDef, OperationType,
+    RootOperationTypeDef, SchemaDef, Span, TypeDef,
+};
+
+use crate::core::common::error::Diagnostic;
+use crate::core::parse::ParseResult;
+
+/// Folds every `extend ...` definition into the definition it extends.
+///
+/// Extensions may appear before or after the definition they extend. An
+/// extension of an unknown definition, or of a definition of another kind, is
+/// reported and dropped. `extend schema` in a document without a `schema`
+/// definition extends the implicit schema of its `Query`, `Mutation` and
+/// `Subscription` types. Fields, interfaces, values, members and root
+/// operation types that are already defined are reported, and the rest of the
+/// extension is still applied.
+pub fn merge_extensions(document: Document) -> ParseResult<Document> {
+    let (extensions, mut definitions): (Vec<Definition>, Vec<Definition>) = document
+        .definitions
+        .into_iter()
+        .partition(|def| matches!(def, Definition::Extension(_)));
+    let mut errors: Vec<Diagnostic> = Vec::new();
+
+    for extension in extensions {
+        let Definition::Extension(extension) = extension else {
+            continue;
+        };
+
+        let target: Option<&mut Definition> = definitions.iter_mut().find(|def| match &extension {
+            Extension::Schema(_) => matches!(def, Definition::Schema(_)),
+            _ => def.name().is_some() && def.name() == extension.name(),
+        });
+
+        match (extension, target) {
+            (Extension::Type(ext), Some(Definition::Type(base)))
+            | (Extension::Interface(ext), Some(Definition::Interface(base))) => {
+                merge_fields(base, ext, &mut errors)
+            }
+            (Extension::Input(ext), Some(Definition::Input(base))) => {
+                merge_input_fields(base, ext, &mut errors)
+            }
+            (Extension::Scalar(ext), Some(Definition::Scalar(base))) => {
+                append_directives(&mut base.directives, ext.directives)
+            }
+            (Extension::Union(ext), Some(Definition::Union(base))) => {
+                append_directives(&mut base.directives, ext.directives);
+                for member in ext.members {
+                    if base
+                        .members
+                        .iter()
+                        .any(|m| m.named_type() == member.named_type())
+                    {
+                        errors.push(
+                            Diagnostic::error(
+                                "conflicting-union-member",
+                                format!(
+                                    "'{}' is already a member of union '{}'",
+                                    member.named_type(),
+                                    base.name
+                                ),
+                            )
+                            .with_primary(ext.span, "added again here")
+                            .with_secondary(base.span, "union defined here"),
+                        );
+                    } else {
+                        base.members.push(member);
+                    }
+                }
+            }
+            (Extension::Enum(ext), Some(Definition::Enum(base))) => {
+                append_directives(&mut base.directives, ext.directives);
+                for value in ext.values {
+                    match base.values.iter().find(|v| v.name == value.name) {
+                        Some(existing) => errors.push(
+                            Diagnostic::error(
+                                "conflicting-enum-value",
+                                format!(
+                                    "Enum value '{}.{}' is already defined",
+                                    base.name, value.name
+                                ),
+                            )
+                            .with_primary(value.span, "defined again here")
+                            .with_secondary(existing.span, "first defined here"),
+                        ),
+                        None => base.values.push(value),
+                    }
+                }
+            }
+            (Extension::Schema(ext), Some(Definition::Schema(base))) => {
+                merge_schema(base, ext, &mut errors)
+            }
+            (extension, Some(base)) => errors.push(
+                Diagnostic::error(
+                    "extension-kind-mismatch",
+                    format!(
+                        "Cannot extend {} '{}' with `extend {}`",
+                        base.keyword(),
+                        extension.name().unwrap_or_default(),
+                        extension.keyword()
+                    ),
+                )
+                .with_primary(extension.span(), "")
+                .with_secondary(base.span(), format!("defined as {} here", base.keyword())),
+            ),
+            (Extension::Schema(ext), None) => {
+                let mut base: SchemaDef = implicit_schema(&definitions, ext.span);
+                merge_schema(&mut base, ext, &mut errors);
+                definitions.push(Definition::Schema(base));
+            }
+            (extension, None) => errors.push(
+                Diagnostic::error(
+                    "unknown-type",
+                    format!(
+                        "Cannot extend unknown type '{}'",
+                        extension.name().unwrap_or_default()
+                    ),
+                )
+                .with_primary(extension.span(), "not defined in this document"),
+            ),
+        }
+    }
+
+    ParseResult {
+        output: Document {
+            definitions,
+            comments: document.comments,
+        },
+        errors,
+    }
+}
+
+/// The schema a document without a `schema` definition implies: the object
+/// types named `Query`, `Mutation` and `Subscription`, if present, are its
+/// root operation types.
+fn implicit_schema(definitions: &[Definition], span: Span) -> SchemaDef {
+    let operation_types: Vec<RootOperationTypeDef> = OperationType::ALL
+        .into_iter()
+        .filter_map(|operation| {
+            definitions.iter().find_map(|def| match def {
+                Definition::Type(def) if def.name == operation.default_type_name() => {
+                    Some(RootOperationTypeDef {
+                        operation,
+                        type_name: def.name.clone(),
+                        span: def.span,
+                        comments: Comments::default(),
+                    })
+                }
+                _ => None,
+            })
+        })
+        .collect();
+
+    SchemaDef {
+        operation_types,
+        directives: None,
+        description: None,
+        span,
+        comments: Comments::default(),
+    }
+}
+
+fn merge_schema(base: &mut SchemaDef, ext: SchemaDef, errors: &mut Vec<Diagnostic>) {
+    append_directives(&mut base.directives, ext.directives);
+
+    for root in ext.operation_types {
+        match base
+            .operation_types
+            .iter()
+            .find(|r| r.operation == root.operation)
+        {
+            Some(existing) => errors.push(
+                Diagnostic::error(
+                    "conflicting-root-operation",
+                    format!(
+                        "The schema already has a {} root type",
+                        root.operation.as_str()
+                    ),
+                )
+                .with_primary(root.span, "defined again here")
+                .with_secondary(existing.span, "first defined here"),
+            ),
+            None => base.operation_types.push(root),
+        }
+    }
+}
+
+fn merge_fields(base: &mut TypeDef, ext: TypeDef, errors: &mut Vec<Diagnostic>) {
+    append_directives(&mut base.directives, ext.directives);
+
+    for interface in ext.interfaces {
+        if base.interfaces.contains(&interface) {
+            errors.push(
+                Diagnostic::error(
+                    "conflicting-interface",
+                    format!("'{}' already implements '{}'", base.name, interface),
+                )
+                .with_primary(ext.span, "implemented again here")
+                .with_secondary(base.span, "first implemented here"),
+            );
+        } else {
+            base.interfaces.push(interface);
+        }
+    }
+
+    for field in ext.fields {
+        match base.fields.iter().find(|f| f.name == field.name) {
+            Some(existing) => errors.push(
+                Diagnostic::error(
+                    "conflicting-field",
+                    format!("Field '{}.{}' is already defined", base.name, field.name),
+                )
+                .with_primary(field.span, "defined again here")
+                .with_secondary(existing.span, "first defined here"),
+            ),
+            None => base.fields.push(field),
+        }
+    }
+}
+
+fn merge_input_fields(
+    base: &mut InputObjectDef,
+    ext: InputObjectDef,
+    errors: &mut Vec<Diagnostic>,
+) {
+    append_directives(&mut base.directives, ext.directives);
+
+    for field in ext.fields {
+        match base.fields.iter().find(|f| f.name == field.name) {
+            Some(existing) => errors.push(
+                Diagnostic::error(
+                    "conflicting-field",
+                    format!("Field '{}.{}' is already defined", base.name, field.name),
+                )
+                .with_primary(field.span, "defined again here")
+                .with_secondary(existing.span, "first defined here"),
+            ),
+            None => base.fields.push(field),
+        }
+    }
+}
+
+fn append_directives(base: &mut Option<Vec<Directive>>, extra: Option<Vec<Directive>>) {
+    if let Some(extra) = extra {
+        base.get_or_insert_with(Vec::new).extend(extra);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::parse::parse_document;
+
+    fn merge(source: &str) -> ParseResult<Document> {
+        let parsed = parse_document(&mut Parser::new(source));
+        assert!(!parsed.has_errors(), "{:?}", parsed.errors);
+        merge_extensions(parsed.output)
+    }
+
+    fn type_def<'a>(document: &'a Document, name: &str) -> &'a TypeDef {
+        document
+            .definitions
+            .iter()
+            .find_map(|def| match def {
+                Definition::Type(def) | Definition::Interface(def) if def.name == name => Some(def),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_merges_fields_and_directives() {
+        let result = merge(
+            r#"
+            extend type Query @cached { posts: [Post] }
+            type Query implements Node { me: User }
+            extend type Query implements Root { feed: [Post] }
+            "#,
+        );
+
+        assert!(!result.has_errors(), "{:?}", result.errors);
+        assert_eq!(result.output.definitions.len(), 1);
+
+        let query = type_def(&result.output, "Query");
+        let fields: Vec<&str> = query.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, vec!["me", "posts", "feed"]);
+        assert_eq!(query.interfaces, vec!["Node", "Root"]);
+        assert_eq!(query.directives.as_ref().unwrap()[0].name, "cached");
+    }
+
+    #[test]
+    fn test_merges_enum_union_and_schema() {
+        let result = merge(
+            r#"
+            schema { query: Query }
+            type Query { a: ID }
+            type Events { b: ID }
+            enum Role { ADMIN }
+            union Media = Photo
+            extend enum Role { USER }
+            extend union Media = Video
+            extend schema { subscription: Events }
+            "#,
+        );
+
+        assert!(!result.has_errors(), "{:?}", result.errors);
+        let document = result.output;
+        for def in &document.definitions {
+            match def {
+                Definition::Enum(def) => assert_eq!(def.values.len(), 2),
+                Definition::Union(def) => assert_eq!(def.members.len(), 2),
+                Definition::Schema(def) => assert_eq!(def.operation_types.len(), 2),
+                _ => {}
+            }
+        }
+        assert_eq!(
+            document.root_operation_type(graphqlgen_schema::ast::OperationType::Subscription),
+            Some("Events")
+        );
+    }
+
+    #[test]
+    fn test_reports_conflicting_fields() {
+        let result = merge("type User { id: ID }\nextend type User { id: ID name: String }");
+
+        assert_eq!(result.errors.len(), 1);
+        let error = &result.errors[0];
+        assert_eq!(error.code, "conflicting-field");
+        assert_eq!(error.message, "Field 'User.id' is already defined");
+        assert_eq!(error.span().unwrap().start.line, 2);
+        assert_eq!(error.secondary[0].span.start.line, 1);
+
+        let user = type_def(&result.output, "User");
+        assert_eq!(user.fields.len(), 2);
+    }
+
+    #[test]
+    fn test_merges_input_fields() {
+        let result = merge(
+            "input Filter { name: String }\nextend input Filter @oneOf { name: ID limit: Int = 10 }",
+        );
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            result.errors[0].message,
+            "Field 'Filter.name' is already defined"
+        );
+
+        let Definition::Input(filter) = &result.output.definitions[0] else {
+            panic!("Expected InputObjectDef");
+        };
+        let fields: Vec<&str> = filter.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(fields, vec!["name", "limit"]);
+        assert_eq!(filter.directives.as_ref().unwrap()[0].name, "oneOf");
+    }
+
+    #[test]
+    fn test_reports_unknown_and_mismatched_extensions() {
+        let result = merge(
+            r#"
+            enum Role { ADMIN }
+            extend type Role { id: ID }
+            extend input Missing { id: ID }
+            "#,
+        );
+
+        let codes: Vec<&str> = result.errors.iter().map(|e| e.code.as_str()).collect();
+        assert_eq!(codes, vec!["extension-kind-mismatch", "unknown-type"]);
+        assert_eq!(
+            result.errors[0].message,
+            "Cannot extend enum 'Role' with `extend type`"
+        );
+        assert_eq!(
+            result.errors[1].message,
+            "Cannot extend unknown type 'Missing'"
+        );
+        assert_eq!(result.output.definitions.len(), 1);
+    }
+
+    #[test]
+    fn test_extends_implicit_schema() {
+        let result = merge(
+            r#"
+            extend schema @link(url: "https://specs.apollo.dev/federation/v2.3")
+            type Query { a: ID }
+            type Mutation { b: ID }
+            type Events { c: ID }
+            extend schema { subscription: Events }
+            "#,
+        );
+
+        assert!(!result.has_errors(), "{:?}", result.errors);
+        let Some(Definition::Schema(schema)) = result.output.definitions.last() else {
+            panic!("Expected SchemaDef");
+        };
+        assert_eq!(schema.directives.as_ref().unwrap()[0].name, "link");
+        assert_eq!(
+            result.output.root_operation_types(),
+            vec![
+                (OperationType::Query, "Query"),
+                (OperationType::Mutation, "Mutation"),
+                (OperationType::Subscription, "Events"),
+            ]
+        );
+
+        let result = merge("type Query { a: ID }\nextend schema { query: Root }");
+        assert_eq!(
+            result.errors[0].message,
+            "The schema already has a query root type"
+        );
+        assert_eq!(result.errors[0].secondary[0].span.start.line, 1);
+    }
+}
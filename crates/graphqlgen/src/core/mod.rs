@@ -1,3 +1,7 @@
 pub mod common;
 pub mod document;
+pub mod executable;
+pub mod format;
+pub mod merge;
 pub mod parse;
+pub mod validate;
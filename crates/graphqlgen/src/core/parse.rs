@@ -1,88 +1,715 @@
-use anyhow::Result;
-use graphqlgen_schema::ast::{Definition, Document};
-use log::error;
+use graphqlgen_schema::ast::{
+    Definition, Document, ExecutableDefinition, ExecutableDocument, OperationType,
+};
 
+use crate::core::common::comments::attach_comments;
+use crate::core::common::error::{Diagnostic, Result};
 use crate::core::common::lexers::Lexer;
-use crate::core::common::token::Token;
+use crate::core::common::parse::expect::{describe, describe_found};
+use crate::core::common::parse::recover::{recover, recover_executable};
+use crate::core::common::parser::Parser;
+use crate::core::common::token::{SpannedToken, Token};
 
+use crate::core::document::directive_def;
+use crate::core::document::extension;
 use crate::core::document::input;
 use crate::core::document::interface;
 use crate::core::document::scalar;
+use crate::core::document::schema;
 use crate::core::document::type_def;
 use crate::core::document::union;
 
 use super::document::enum_def;
-pub fn parse_document(tokens: Vec<Token>) -> Result<Document> {
+use super::executable::{fragment, operation};
+
+/// Everything that could be parsed, together with every error that was
+/// recovered from along the way.
+#[derive(Debug, Clone, Default)]
+pub struct ParseResult<T> {
+    pub output: T,
+    pub errors: Vec<Diagnostic>,
+}
+
+impl<T> ParseResult<T> {
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Returns the output only if no errors were found.
+    pub fn into_result(self) -> std::result::Result<T, Vec<Diagnostic>> {
+        if self.errors.is_empty() {
+            Ok(self.output)
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+/// Parses a schema document. A definition that fails to parse is reported and
+/// skipped, and parsing resumes at the next definition, so the returned
+/// document holds every definition that was valid. Errors from the lexer are
+/// reported alongside, in source order. Comments kept by
+/// [`Parser::with_comments`] are attached to the parsed nodes.
+pub fn parse_document(parser: &mut Parser) -> ParseResult<Document> {
     let mut definitions: Vec<Definition> = Vec::new();
-    let mut index: usize = 0;
-    let mut pending_description: Option<String> = None;
-
-    while index < tokens.len() {
-        match &tokens[index] {
-            Token::Description(desc) => {
-                // Save description and move to the next token
-                pending_description = Some(desc.clone());
-                index += 1;
-            }
+    let mut errors: Vec<Diagnostic> = Vec::new();
 
-            Token::Name(name) if name == "type" => {
-                let def: Definition =
-                    type_def::parse_type(&tokens, &mut index, pending_description.take())?;
-                definitions.push(def);
-            }
+    while !parser.at_end() {
+        let start: usize = parser.position();
+        parser.release(start);
 
-            Token::Name(name) if name == "input" => {
-                let def: Definition =
-                    input::parse_input(&tokens, &mut index, pending_description.take())?;
-                definitions.push(def);
+        match parse_definition(parser) {
+            Ok(definition) => definitions.push(definition),
+            Err(diagnostic) => {
+                errors.push(diagnostic);
+                recover(parser, start);
             }
+        }
+    }
 
-            Token::Name(name) if name == "scalar" => {
-                let def: Definition =
-                    scalar::parse_scalar(&tokens, &mut index, pending_description.take())?;
-                definitions.push(def);
-            }
+    let mut document = Document {
+        definitions,
+        comments: Vec::new(),
+    };
+    attach_comments(&mut document, parser.take_comments());
 
-            Token::Name(name) if name == "interface" => {
-                let def: Definition =
-                    interface::parse_interface(&tokens, &mut index, pending_description.take())?;
-                definitions.push(def);
-            }
+    ParseResult {
+        output: document,
+        errors: with_lex_errors(parser, errors),
+    }
+}
 
-            Token::Name(name) if name == "union" => {
-                let def: Definition =
-                    union::parse_union(&tokens, &mut index, pending_description.take())?;
-                definitions.push(def);
-            }
+fn parse_definition(parser: &mut Parser) -> Result<Definition> {
+    let description: Option<String> = parser.take_description();
 
-            Token::Name(name) if name == "enum" => {
-                let def: Definition =
-                    enum_def::parse_enum(&tokens, &mut index, pending_description.take())?;
-                definitions.push(def);
-            }
+    match parser.peek() {
+        Some(Token::Name(name)) if *name == "type" => type_def::parse_type(parser, description),
+        Some(Token::Name(name)) if *name == "input" => input::parse_input(parser, description),
+        Some(Token::Name(name)) if *name == "scalar" => scalar::parse_scalar(parser, description),
+        Some(Token::Name(name)) if *name == "interface" => {
+            interface::parse_interface(parser, description)
+        }
+        Some(Token::Name(name)) if *name == "union" => union::parse_union(parser, description),
+        Some(Token::Name(name)) if *name == "enum" => enum_def::parse_enum(parser, description),
+        Some(Token::Name(name)) if *name == "schema" => schema::parse_schema(parser, description),
+        Some(Token::Name(name)) if *name == "directive" => {
+            directive_def::parse_directive_def(parser, description)
+        }
+        Some(Token::Name(name)) if *name == "extend" => {
+            extension::parse_extension(parser, description)
+        }
+        Some(other) => {
+            Err(parser.unexpected(format!("Expected a definition, found {}", describe(other))))
+        }
+        None => Err(parser.unexpected("Expected a definition after description")),
+    }
+}
+
+/// Parses a document of operations and fragments, such as a client's
+/// `.graphql` query files. Errors are recovered from like in
+/// [`parse_document`].
+pub fn parse_executable_document(parser: &mut Parser) -> ParseResult<ExecutableDocument> {
+    let mut definitions: Vec<ExecutableDefinition> = Vec::new();
+    let mut errors: Vec<Diagnostic> = Vec::new();
 
-            _ => {
-                index += 1;
-                pending_description = None;
+    while !parser.at_end() {
+        let start: usize = parser.position();
+        parser.release(start);
+
+        match parse_executable_definition(parser) {
+            Ok(definition) => definitions.push(definition),
+            Err(diagnostic) => {
+                errors.push(diagnostic);
+                recover_executable(parser, start);
             }
         }
     }
 
-    Ok(Document { definitions })
+    ParseResult {
+        output: ExecutableDocument { definitions },
+        errors: with_lex_errors(parser, errors),
+    }
+}
+
+fn parse_executable_definition(parser: &mut Parser) -> Result<ExecutableDefinition> {
+    match parser.peek() {
+        Some(Token::Name(name)) if *name == "fragment" => fragment::parse_fragment(parser),
+        Some(Token::BraceOpen) => operation::parse_operation(parser),
+        Some(Token::Name(name)) if OperationType::from_keyword(name).is_some() => {
+            operation::parse_operation(parser)
+        }
+        other => Err(parser
+            .unexpected(format!(
+                "Expected an operation or fragment, found {}",
+                describe_found(other)
+            ))
+            .with_help("expected `query`, `mutation`, `subscription`, `fragment` or `{`")),
+    }
+}
+
+/// Merges the lexer's errors into `errors`, ordered by where they start.
+fn with_lex_errors(parser: &mut Parser, mut errors: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    errors.extend(parser.take_lex_errors());
+    errors.sort_by_key(|e| e.span().map(|span| span.start.offset));
+    errors
+}
+
+/// Splits `schema_content` into tokens. Invalid characters are reported and
+/// skipped, so the returned tokens cover everything that could be lexed.
+pub fn generate_tokens(schema_content: &str) -> ParseResult<Vec<SpannedToken<'_>>> {
+    let mut result: ParseResult<Vec<SpannedToken>> = ParseResult::default();
+
+    for token in Lexer::new(schema_content) {
+        match token {
+            Ok(token) => result.output.push(token),
+            Err(diagnostic) => result.errors.push(diagnostic),
+        }
+    }
+
+    result
 }
 
-pub fn generate_tokens(schema_content: &str) -> Result<Vec<Token>> {
-    let lexer: Lexer<'_> = Lexer::new(&schema_content);
-    let tokens: Vec<_> = lexer
-        .filter_map(|t: Result<Token, String>| match t {
-            Ok(t) if t != Token::EOF => Some(t),
-            Ok(_) => None,
-            Err(e) => {
-                error!("Lexer error: {}", e);
-                std::process::exit(1);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use graphqlgen_schema::printer::print_document;
+
+    fn parse(source: &str) -> ParseResult<Document> {
+        parse_document(&mut Parser::new(source))
+    }
+
+    fn names(document: &Document) -> Vec<String> {
+        document
+            .definitions
+            .iter()
+            .map(|def| match def {
+                Definition::Type(t) | Definition::Interface(t) => t.name.clone(),
+                Definition::Input(i) => i.name.clone(),
+                Definition::Scalar(s) => s.name.clone(),
+                Definition::Union(u) => u.name.clone(),
+                Definition::Enum(e) => e.name.clone(),
+                Definition::Schema(_) => "schema".to_string(),
+                Definition::Directive(d) => format!("@{}", d.name),
+                Definition::Extension(e) => format!("extend {}", e.name().unwrap_or("schema")),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_reports_every_broken_definition() {
+        let result = parse(
+            r#"
+            type A { id: : ID }
+            type B { id: ID }
+            enum C { ONE : }
+            scalar D
+            input E { value String }
+            "#,
+        );
+
+        assert_eq!(result.errors.len(), 3, "{:?}", result.errors);
+        assert_eq!(names(&result.output), vec!["B", "D"]);
+
+        let lines: Vec<usize> = result
+            .errors
+            .iter()
+            .map(|e| e.span().unwrap().start.line)
+            .collect();
+        assert_eq!(lines, vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_recovers_from_missing_closing_brace() {
+        let result = parse(
+            r#"
+            type A {
+                id: ID
+                name: [String
+
+            """B"""
+            type B { id: ID }
+            "#,
+        );
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(names(&result.output), vec!["B"]);
+        if let Definition::Type(b) = &result.output.definitions[0] {
+            assert_eq!(b.description.as_deref(), Some("B"));
+        }
+    }
+
+    #[test]
+    fn test_recovers_from_described_definition() {
+        let result = parse("\"\"\"d\"\"\" type A { id: : ID }\ntype B { id: ID }");
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(names(&result.output), vec!["B"]);
+    }
+
+    #[test]
+    fn test_recovers_from_quoted_described_definition() {
+        let result = parse("\"d\" type A { id: : ID }\ntype B { id: ID }");
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(names(&result.output), vec!["B"]);
+    }
+
+    #[test]
+    fn test_resumes_at_definition_misread_as_field() {
+        let result = parse("type A { id: ID\ntype B { id: ID }");
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].message, "Expected `:`, found name `B`");
+        assert_eq!(names(&result.output), vec!["B"]);
+    }
+
+    #[test]
+    fn test_skips_nested_braces_in_broken_definition() {
+        let result = parse(
+            r#"
+            type A @meta(value: { nested: { deep: 1 } }) { id: ID: }
+            type B { id: ID }
+            "#,
+        );
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(names(&result.output), vec!["B"]);
+    }
+
+    #[test]
+    fn test_fields_named_like_keywords_do_not_resynchronise() {
+        let result = parse(
+            r#"
+            type A { type: String, input: , enum: Int }
+            type B { id: ID }
+            "#,
+        );
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(names(&result.output), vec!["B"]);
+    }
+
+    #[test]
+    fn test_stray_tokens_at_top_level() {
+        let result = parse("} ] type A { id: ID } ) scalar B");
+
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(names(&result.output), vec!["A", "B"]);
+        assert!(result.errors[0]
+            .message
+            .starts_with("Expected a definition, found `}`"));
+    }
+
+    #[test]
+    fn test_string_descriptions_on_definitions() {
+        let result = parse(
+            r#"
+            "Search results"
+            union Result = A | B
+            "Restricts access"
+            directive @auth on FIELD_DEFINITION
+            type A { id: ID: }
+            "Still attached after recovery"
+            scalar B
+            "#,
+        );
+
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(names(&result.output), vec!["Result", "@auth", "B"]);
+
+        let descriptions: Vec<Option<&str>> = result
+            .output
+            .definitions
+            .iter()
+            .map(|def| match def {
+                Definition::Union(u) => u.description.as_deref(),
+                Definition::Directive(d) => d.description.as_deref(),
+                Definition::Scalar(s) => s.description.as_deref(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                Some("Search results"),
+                Some("Restricts access"),
+                Some("Still attached after recovery")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_schema_definition() {
+        let result = parse(
+            r#"
+            """The API"""
+            schema @entry { query: RootQuery mutation: RootMutation }
+            type RootQuery { me: ID }
+            type RootMutation { logout: Boolean }
+            type Query { unused: ID }
+            "#,
+        );
+
+        assert!(!result.has_errors(), "{:?}", result.errors);
+        let document = result.output;
+        assert_eq!(
+            names(&document),
+            vec!["schema", "RootQuery", "RootMutation", "Query"]
+        );
+        if let Definition::Schema(schema) = &document.definitions[0] {
+            assert_eq!(schema.description.as_deref(), Some("The API"));
+            assert_eq!(schema.directives.as_ref().unwrap()[0].name, "entry");
+        }
+
+        assert_eq!(
+            document.root_operation_type(OperationType::Query),
+            Some("RootQuery")
+        );
+        assert_eq!(
+            document.root_operation_type(OperationType::Mutation),
+            Some("RootMutation")
+        );
+        assert_eq!(
+            document.root_operation_type(OperationType::Subscription),
+            None
+        );
+    }
+
+    #[test]
+    fn test_root_operation_types_default_to_spec_names() {
+        let document = parse("type Query { a: ID } type Subscription { b: ID }").output;
+
+        assert_eq!(
+            document.root_operation_types(),
+            vec![
+                (OperationType::Query, "Query"),
+                (OperationType::Subscription, "Subscription"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recovers_after_broken_schema_definition() {
+        let result = parse("schema { queries: Query } type Query { a: ID }");
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(names(&result.output), vec!["Query"]);
+    }
+
+    #[test]
+    fn test_parse_directive_definitions() {
+        let result = parse(
+            r#"
+            """Restricts access"""
+            directive @auth(requires: Role = ADMIN) on OBJECT | FIELD_DEFINITION
+            directive @tag(name: String!) repeatable on
+              | OBJECT
+              | INTERFACE
+            type Query @auth { id: ID }
+            directive @broken on NOWHERE
+            enum Role { ADMIN USER }
+            "#,
+        );
+
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(
+            names(&result.output),
+            vec!["@auth", "@tag", "Query", "Role"]
+        );
+
+        if let Definition::Directive(tag) = &result.output.definitions[1] {
+            assert!(tag.repeatable);
+            assert_eq!(tag.locations.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_extensions_are_not_parsed_as_definitions() {
+        let result = parse(
+            r#"
+            type Query { me: ID }
+            extend type Query { feed: [ID] }
+            extend schema @public
+            extend enum Role { GUEST: }
+            extend union Media = Video
+            "#,
+        );
+
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(
+            names(&result.output),
+            vec!["Query", "extend Query", "extend schema", "extend Media"]
+        );
+    }
+
+    #[test]
+    fn test_extension_with_description() {
+        let result = parse("type A { a: ID }\n\"\"\" More \"\"\"\nextend type A { b: ID }");
+
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            result.errors[0].message,
+            "Extensions cannot have a description"
+        );
+        assert_eq!(names(&result.output), vec!["A", "extend A"]);
+    }
+
+    #[test]
+    fn test_parse_executable_document() {
+        let result = parse_executable_document(&mut Parser::new(
+            r#"
+            query GetUser($id: ID!) { user(id: $id) { ...UserFields } }
+            mutation { logout }
+            fragment UserFields on User { id name }
+            { me { id } }
+            "#,
+        ));
+
+        assert!(!result.has_errors(), "{:?}", result.errors);
+        let kinds: Vec<String> = result
+            .output
+            .definitions
+            .iter()
+            .map(|def| match def {
+                ExecutableDefinition::Operation(op) => format!(
+                    "{} {}",
+                    op.operation.as_str(),
+                    op.name.as_deref().unwrap_or("-")
+                ),
+                ExecutableDefinition::Fragment(f) => format!("fragment {}", f.name),
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                "query GetUser",
+                "mutation -",
+                "fragment UserFields",
+                "query -"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recovers_in_executable_document() {
+        let result = parse_executable_document(&mut Parser::new(
+            r#"
+            query A { user(id: ) { query } }
+            type B { id }
+            query C { a { b } }
+            fragment D on User { id { } }
+            mutation E { f }
+            "#,
+        ));
+
+        let lines: Vec<usize> = result
+            .errors
+            .iter()
+            .map(|e| e.span().unwrap().start.line)
+            .collect();
+        assert_eq!(lines, vec![2, 3, 5], "{:?}", result.errors);
+        assert_eq!(result.output.definitions.len(), 2);
+    }
+
+    #[test]
+    fn test_collects_lexer_errors() {
+        let tokens = generate_tokens("type A { id: ID § }\ntype B { name: String ° }");
+
+        assert_eq!(tokens.errors.len(), 2);
+        assert_eq!(tokens.errors[1].span().unwrap().start.line, 2);
+
+        let result = parse_document(&mut Parser::from_tokens(tokens.output));
+        assert!(!result.has_errors());
+        assert_eq!(names(&result.output), vec!["A", "B"]);
+
+        let result = parse("type A { id: ID § }\ntype B { name: String: ° }");
+        let lines: Vec<usize> = result
+            .errors
+            .iter()
+            .map(|e| e.span().unwrap().start.line)
+            .collect();
+        assert_eq!(lines, vec![1, 2, 2]);
+        assert_eq!(names(&result.output), vec!["A"]);
+    }
+
+    /// Drops every `span` so documents parsed from different text compare
+    /// equal.
+    fn without_spans(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => map
+                .into_iter()
+                .filter(|(key, _)| key != "span")
+                .map(|(key, value)| (key, without_spans(value)))
+                .collect(),
+            serde_json::Value::Array(items) => items.into_iter().map(without_spans).collect(),
+            other => other,
+        }
+    }
+
+    /// Parses `source`, prints it and parses the output again, expecting the
+    /// same document and a stable printed form.
+    fn assert_round_trips(source: &str) -> String {
+        let document: Document = parse(source).into_result().unwrap();
+        let printed: String = print_document(&document);
+
+        let reparsed: Document = parse(&printed)
+            .into_result()
+            .unwrap_or_else(|errors| panic!("{:?} in\n{}", errors, printed));
+        assert_eq!(
+            without_spans(serde_json::to_value(&reparsed).unwrap()),
+            without_spans(serde_json::to_value(&document).unwrap()),
+            "printed as\n{}",
+            printed
+        );
+        assert_eq!(print_document(&reparsed), printed);
+        printed
+    }
+
+    #[test]
+    fn test_round_trip_every_definition_kind() {
+        assert_round_trips(
+            r#"
+            "The root"
+            schema @link(url: "https://specs.example/v1", import: ["@key"]) { query: Query mutation: Mutation }
+
+            """
+            Something with an id.
+
+              Indented detail.
+            """
+            interface Node { id: ID! }
+
+            interface Entity implements Node @key(fields: "id") { id: ID! }
+
+            type Query implements Node & Entity {
+              id: ID!
+              "Newest first"
+              posts(
+                """Page size"""
+                first: Int = 10 @deprecated
+                order: Order = DESC,
+                filter: PostFilter = { tags: ["news", "tech"], authors: [] }
+              ): [[Post!]]! @cost(weight: -2)
+              search(term: String!): [SearchResult]
+            }
+
+            input PostFilter @oneOf { tags: [String!] = ["news"] authors: [ID] @deprecated(reason: "Use `by`.") }
+
+            scalar Date @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+
+            union SearchResult @tag(name: "x") = | Post | User
+
+            enum Order {
+              "Oldest first"
+              ASC
+              DESC @deprecated(reason: "Use \"ASC\"\n")
+            }
+
+            directive @cost(weight: Int = 1, "Why" reason: String) repeatable on FIELD_DEFINITION | OBJECT
+
+            extend schema @tag { subscription: Subscription }
+            extend type Query @tag { me: User }
+            extend interface Node @tag
+            extend union SearchResult = Comment
+            extend enum Order { RANDOM }
+            extend input PostFilter { limit: Int }
+            extend scalar Date @tag
+            "#,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_descriptions() {
+        let printed: String = assert_round_trips(
+            r#"
+            "  leading spaces"
+            scalar A
+
+            "\nleading blank line"
+            scalar B
+
+            "two\n  lines"
+            scalar C
+
+            "  both\n  indented"
+            scalar D
+
+            "tab\tand \\ backslash \u0001"
+            scalar E
+
+            "ends with \"quote\""
+            scalar F
+
+            """Has \""" inside"""
+            scalar G
+
+            "crlf\r\nline"
+            scalar H
+            "#,
+        );
+
+        assert!(printed.starts_with("\"\"\"  leading spaces\"\"\"\nscalar A\n"));
+        assert!(printed.contains("\"\"\"\ntwo\n  lines\n\"\"\"\nscalar C"));
+        assert!(printed.contains("\"  both\\n  indented\"\nscalar D"));
+    }
+
+    #[test]
+    fn test_round_trip_comments() {
+        let parse_with_comments = |source: &str| {
+            parse_document(&mut Parser::with_comments(source))
+                .into_result()
+                .unwrap()
+        };
+        let document: Document = parse_with_comments(
+            r#"
+            # License
+
+            # Roots
+            schema { # roots
+              query: Query # reads
             }
-        })
-        .collect();
 
-    Ok(tokens)
+            type Query {
+              # Posts
+              posts(
+                # Page
+                first: Int # size
+              ): [Post] # list
+              # more later
+            }
+
+            enum Order { ASC # up
+              DESC }
+            union U = A # members
+              | B
+            # trailing notes
+            "#,
+        );
+        let printed: String = print_document(&document);
+        let reparsed: Document = parse_with_comments(&printed);
+
+        assert_eq!(
+            without_spans(serde_json::to_value(&reparsed).unwrap()),
+            without_spans(serde_json::to_value(&document).unwrap()),
+            "printed as\n{}",
+            printed
+        );
+        assert_eq!(print_document(&reparsed), printed);
+    }
+
+    #[test]
+    fn test_print_is_canonical() {
+        let document: Document = parse(
+            "type   User@key(fields:\"id\"){id:ID!,friends(first:Int=10):[User]}enum Role{ADMIN USER}",
+        )
+        .into_result()
+        .unwrap();
+
+        assert_eq!(
+            print_document(&document),
+            "type User @key(fields: \"id\") {\n  id: ID!\n  friends(first: Int = 10): [User]\n}\n\n\
+             enum Role {\n  ADMIN\n  USER\n}\n"
+        );
+    }
 }
@@ -0,0 +1,1050 @@
+use std::collections::{HashMap, HashSet};
+
+use graphqlgen_schema::ast::{
+    Definition, DirectiveDef, Document, EnumDef, Field, InputObjectDef, InputValue, SchemaDef,
+    Span, TypeDef, TypeRef, UnionDef,
+};
+use graphqlgen_schema::built_ins::{built_in_directives, BUILT_IN_SCALARS};
+use graphqlgen_schema::printer::print_type_ref;
+
+use crate::core::common::error::Diagnostic;
+
+/// Where a type is used: field types are outputs, argument and input field
+/// types are inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Output,
+    Input,
+}
+
+/// Checks a document against the type system validation rules of the GraphQL
+/// spec, returning every violation sorted by position.
+///
+/// Expects extensions to have been merged into the types they extend by
+/// [`merge_extensions`](super::merge::merge_extensions); any left over are
+/// ignored.
+pub fn validate_document(document: &Document) -> Vec<Diagnostic> {
+    let mut validator: Validator = Validator::new(document);
+
+    for definition in &document.definitions {
+        validator.check_definition(definition);
+    }
+    validator.check_input_cycles(document);
+
+    let mut errors: Vec<Diagnostic> = validator.errors;
+    errors.sort_by_key(|error| error.span().map(|span| span.start.offset));
+    errors
+}
+
+struct Validator<'a> {
+    /// The first definition of each named type.
+    types: HashMap<&'a str, &'a Definition>,
+    errors: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    /// Collects the named types of `document`, reporting names defined more
+    /// than once.
+    fn new(document: &'a Document) -> Self {
+        let mut validator: Validator = Validator {
+            types: HashMap::new(),
+            errors: Vec::new(),
+        };
+        let mut directives: HashMap<&str, &DirectiveDef> = HashMap::new();
+        let mut schema: Option<&SchemaDef> = None;
+
+        for definition in &document.definitions {
+            let first: Option<Span> = match definition {
+                Definition::Directive(directive) => directives
+                    .insert(&directive.name, directive)
+                    .map(|first| first.span),
+                Definition::Schema(schema_def) => {
+                    schema.replace(schema_def).map(|first| first.span)
+                }
+                Definition::Extension(_) => None,
+                _ => {
+                    let name: &str = definition.name().unwrap_or_default();
+                    match validator.types.get(name) {
+                        Some(first) => Some(first.span()),
+                        None => {
+                            validator.types.insert(name, definition);
+                            None
+                        }
+                    }
+                }
+            };
+
+            if let Some(first) = first {
+                let message: String = match definition {
+                    Definition::Directive(directive) => {
+                        format!("Directive '@{}' is defined more than once", directive.name)
+                    }
+                    Definition::Schema(_) => "The schema is defined more than once".to_string(),
+                    _ => format!(
+                        "Type '{}' is defined more than once",
+                        definition.name().unwrap_or_default()
+                    ),
+                };
+                validator.errors.push(
+                    Diagnostic::error("duplicate-definition", message)
+                        .with_primary(definition.span(), "defined again here")
+                        .with_secondary(first, "first defined here"),
+                );
+            }
+        }
+
+        validator
+    }
+
+    fn check_definition(&mut self, definition: &'a Definition) {
+        self.check_built_in(definition);
+
+        match definition {
+            Definition::Type(type_def) | Definition::Interface(type_def) => {
+                self.check_name(&type_def.name, type_def.span);
+                self.check_fields(type_def);
+                self.check_interfaces(type_def);
+            }
+            Definition::Input(input) => {
+                self.check_name(&input.name, input.span);
+                self.check_input_fields(input);
+            }
+            Definition::Scalar(scalar) => self.check_name(&scalar.name, scalar.span),
+            Definition::Union(union_def) => self.check_union(union_def),
+            Definition::Enum(enum_def) => self.check_enum(enum_def),
+            Definition::Schema(schema) => self.check_schema(schema),
+            Definition::Directive(directive) => {
+                self.check_name(&directive.name, directive.span);
+                if let Some(arguments) = &directive.arguments {
+                    self.check_arguments(arguments, &format!("@{}", directive.name));
+                }
+            }
+            Definition::Extension(_) => {}
+        }
+    }
+
+    /// Built-in scalars may only be redefined as scalars, and built-in
+    /// directives only in a way that accepts no usage the built-in rejects.
+    fn check_built_in(&mut self, definition: &Definition) {
+        if let Definition::Directive(directive) = definition {
+            let Some(built_in) = built_in_directives()
+                .into_iter()
+                .find(|built_in| built_in.name == directive.name)
+            else {
+                return;
+            };
+            if let Some(mismatch) = directive_mismatch(directive, &built_in) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "incompatible-built-in",
+                        format!(
+                            "Directive '@{}' does not match the built-in directive",
+                            directive.name
+                        ),
+                    )
+                    .with_primary(directive.span, mismatch)
+                    .with_help("remove the definition to use the built-in one"),
+                );
+            }
+            return;
+        }
+
+        let Some(name) = definition.name() else {
+            return;
+        };
+        if BUILT_IN_SCALARS.contains(&name) && !matches!(definition, Definition::Scalar(_)) {
+            self.errors.push(
+                Diagnostic::error(
+                    "incompatible-built-in",
+                    format!(
+                        "'{}' is a built-in scalar and cannot be redefined as {}",
+                        name,
+                        describe(definition)
+                    ),
+                )
+                .with_primary(definition.span(), "")
+                .with_help("rename the type"),
+            );
+        }
+    }
+
+    /// Names starting with `__` are reserved for the introspection system.
+    fn check_name(&mut self, name: &str, span: Span) {
+        if name.starts_with("__") {
+            self.errors.push(
+                Diagnostic::error(
+                    "reserved-name",
+                    format!("Name '{}' must not begin with '__'", name),
+                )
+                .with_primary(span, "")
+                .with_help("names starting with `__` are reserved for introspection"),
+            );
+        }
+    }
+
+    fn check_fields(&mut self, type_def: &TypeDef) {
+        if type_def.fields.is_empty() {
+            self.errors.push(
+                Diagnostic::error(
+                    "empty-type",
+                    format!("Type '{}' must define one or more fields", type_def.name),
+                )
+                .with_primary(type_def.span, ""),
+            );
+        }
+
+        let mut seen: HashMap<&str, Span> = HashMap::new();
+        for field in &type_def.fields {
+            let path: String = format!("{}.{}", type_def.name, field.name);
+
+            if let Some(first) = seen.insert(&field.name, field.span) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "duplicate-field",
+                        format!("Field '{}' is defined more than once", path),
+                    )
+                    .with_primary(field.span, "defined again here")
+                    .with_secondary(first, "first defined here"),
+                );
+            }
+            self.check_name(&field.name, field.span);
+            self.check_type_ref(
+                &field.field_type,
+                Position::Output,
+                field.span,
+                &format!("Field '{}'", path),
+            );
+            if let Some(arguments) = &field.arguments {
+                self.check_arguments(arguments, &path);
+            }
+        }
+    }
+
+    fn check_input_fields(&mut self, input: &InputObjectDef) {
+        if input.fields.is_empty() {
+            self.errors.push(
+                Diagnostic::error(
+                    "empty-type",
+                    format!("Input type '{}' must define one or more fields", input.name),
+                )
+                .with_primary(input.span, ""),
+            );
+        }
+
+        let mut seen: HashMap<&str, Span> = HashMap::new();
+        for field in &input.fields {
+            let path: String = format!("{}.{}", input.name, field.name);
+
+            if let Some(first) = seen.insert(&field.name, field.span) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "duplicate-field",
+                        format!("Field '{}' is defined more than once", path),
+                    )
+                    .with_primary(field.span, "defined again here")
+                    .with_secondary(first, "first defined here"),
+                );
+            }
+            self.check_name(&field.name, field.span);
+            self.check_type_ref(
+                &field.value_type,
+                Position::Input,
+                field.span,
+                &format!("Field '{}'", path),
+            );
+        }
+    }
+
+    fn check_arguments(&mut self, arguments: &[InputValue], owner: &str) {
+        let mut seen: HashMap<&str, Span> = HashMap::new();
+
+        for argument in arguments {
+            let path: String = format!("{}({}:)", owner, argument.name);
+
+            if let Some(first) = seen.insert(&argument.name, argument.span) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "duplicate-argument",
+                        format!("Argument '{}' is defined more than once", path),
+                    )
+                    .with_primary(argument.span, "defined again here")
+                    .with_secondary(first, "first defined here"),
+                );
+            }
+            self.check_name(&argument.name, argument.span);
+            self.check_type_ref(
+                &argument.value_type,
+                Position::Input,
+                argument.span,
+                &format!("Argument '{}'", path),
+            );
+        }
+    }
+
+    /// Checks that the type `what` refers to exists and can be used in
+    /// `position`.
+    fn check_type_ref(&mut self, type_ref: &TypeRef, position: Position, span: Span, what: &str) {
+        let name: &str = type_ref.named_type();
+        if BUILT_IN_SCALARS.contains(&name) {
+            return;
+        }
+
+        let Some(definition) = self.types.get(name).copied() else {
+            self.errors.push(
+                Diagnostic::error("unknown-type", format!("Unknown type '{}'", name))
+                    .with_primary(span, format!("{} refers to '{}'", what, name)),
+            );
+            return;
+        };
+
+        let (allowed, expected): (bool, &str) = match position {
+            Position::Output => (!matches!(definition, Definition::Input(_)), "output"),
+            Position::Input => (
+                matches!(
+                    definition,
+                    Definition::Scalar(_) | Definition::Enum(_) | Definition::Input(_)
+                ),
+                "input",
+            ),
+        };
+        if !allowed {
+            self.errors.push(
+                Diagnostic::error(
+                    format!("invalid-{}-type", expected),
+                    format!(
+                        "{} must have an {} type, but '{}' is {}",
+                        what,
+                        expected,
+                        name,
+                        describe(definition)
+                    ),
+                )
+                .with_primary(span, "")
+                .with_secondary(definition.span(), format!("'{}' defined here", name)),
+            );
+        }
+    }
+
+    fn check_union(&mut self, union_def: &UnionDef) {
+        self.check_name(&union_def.name, union_def.span);
+
+        let mut seen: HashSet<&str> = HashSet::new();
+        for member in &union_def.members {
+            let name: &str = member.named_type();
+
+            if !seen.insert(name) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "duplicate-union-member",
+                        format!(
+                            "'{}' is listed more than once in union '{}'",
+                            name, union_def.name
+                        ),
+                    )
+                    .with_primary(union_def.span, ""),
+                );
+                continue;
+            }
+
+            match self.types.get(name).copied() {
+                Some(Definition::Type(_)) => {}
+                Some(definition) => self.errors.push(
+                    Diagnostic::error(
+                        "invalid-union-member",
+                        format!(
+                            "Member '{}' of union '{}' must be an object type, but it is {}",
+                            name,
+                            union_def.name,
+                            describe(definition)
+                        ),
+                    )
+                    .with_primary(union_def.span, "")
+                    .with_secondary(definition.span(), format!("'{}' defined here", name)),
+                ),
+                None if BUILT_IN_SCALARS.contains(&name) => self.errors.push(
+                    Diagnostic::error(
+                        "invalid-union-member",
+                        format!(
+                            "Member '{}' of union '{}' must be an object type, but it is a scalar",
+                            name, union_def.name
+                        ),
+                    )
+                    .with_primary(union_def.span, ""),
+                ),
+                None => self.errors.push(
+                    Diagnostic::error("unknown-type", format!("Unknown type '{}'", name))
+                        .with_primary(
+                            union_def.span,
+                            format!("union '{}' refers to '{}'", union_def.name, name),
+                        ),
+                ),
+            }
+        }
+    }
+
+    fn check_enum(&mut self, enum_def: &EnumDef) {
+        self.check_name(&enum_def.name, enum_def.span);
+        if enum_def.values.is_empty() {
+            self.errors.push(
+                Diagnostic::error(
+                    "empty-type",
+                    format!("Enum '{}' must define one or more values", enum_def.name),
+                )
+                .with_primary(enum_def.span, ""),
+            );
+        }
+
+        let mut seen: HashMap<&str, Span> = HashMap::new();
+        for value in &enum_def.values {
+            if let Some(first) = seen.insert(&value.name, value.span) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "duplicate-enum-value",
+                        format!(
+                            "Enum value '{}.{}' is defined more than once",
+                            enum_def.name, value.name
+                        ),
+                    )
+                    .with_primary(value.span, "defined again here")
+                    .with_secondary(first, "first defined here"),
+                );
+            }
+            if matches!(value.name.as_str(), "true" | "false" | "null") {
+                self.errors.push(
+                    Diagnostic::error(
+                        "invalid-enum-value",
+                        format!("Enum value '{}.{}' is reserved", enum_def.name, value.name),
+                    )
+                    .with_primary(value.span, "")
+                    .with_help("enum values cannot be `true`, `false` or `null`"),
+                );
+            }
+            self.check_name(&value.name, value.span);
+        }
+    }
+
+    fn check_schema(&mut self, schema: &SchemaDef) {
+        let mut seen: HashMap<&str, Span> = HashMap::new();
+
+        for root in &schema.operation_types {
+            let operation: &str = root.operation.as_str();
+
+            if let Some(first) = seen.insert(operation, root.span) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "duplicate-root-type",
+                        format!("The {} root type is defined more than once", operation),
+                    )
+                    .with_primary(root.span, "defined again here")
+                    .with_secondary(first, "first defined here"),
+                );
+            }
+
+            match self.types.get(root.type_name.as_str()).copied() {
+                Some(Definition::Type(_)) => {}
+                Some(definition) => self.errors.push(
+                    Diagnostic::error(
+                        "invalid-root-type",
+                        format!(
+                            "The {} root type must be an object type, but '{}' is {}",
+                            operation,
+                            root.type_name,
+                            describe(definition)
+                        ),
+                    )
+                    .with_primary(root.span, "")
+                    .with_secondary(
+                        definition.span(),
+                        format!("'{}' defined here", root.type_name),
+                    ),
+                ),
+                None => self.errors.push(
+                    Diagnostic::error("unknown-type", format!("Unknown type '{}'", root.type_name))
+                        .with_primary(root.span, format!("{} root type", operation)),
+                ),
+            }
+        }
+    }
+
+    /// Checks that every interface `type_def` implements exists and is
+    /// implemented correctly.
+    fn check_interfaces(&mut self, type_def: &'a TypeDef) {
+        let mut seen: HashSet<&str> = HashSet::new();
+
+        for name in &type_def.interfaces {
+            if !seen.insert(name) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "duplicate-interface",
+                        format!("'{}' implements '{}' more than once", type_def.name, name),
+                    )
+                    .with_primary(type_def.span, ""),
+                );
+                continue;
+            }
+
+            match self.types.get(name.as_str()).copied() {
+                Some(Definition::Interface(interface)) if interface.name == type_def.name => {
+                    self.errors.push(
+                        Diagnostic::error(
+                            "invalid-implementation",
+                            format!("Interface '{}' cannot implement itself", name),
+                        )
+                        .with_primary(type_def.span, ""),
+                    )
+                }
+                Some(Definition::Interface(interface)) => {
+                    self.check_implementation(type_def, interface)
+                }
+                Some(definition) => self.errors.push(
+                    Diagnostic::error(
+                        "invalid-implementation",
+                        format!(
+                            "'{}' can only implement interfaces, but '{}' is {}",
+                            type_def.name,
+                            name,
+                            describe(definition)
+                        ),
+                    )
+                    .with_primary(type_def.span, "")
+                    .with_secondary(definition.span(), format!("'{}' defined here", name)),
+                ),
+                None => self.errors.push(
+                    Diagnostic::error("unknown-type", format!("Unknown type '{}'", name))
+                        .with_primary(
+                            type_def.span,
+                            format!("'{}' implements '{}'", type_def.name, name),
+                        ),
+                ),
+            }
+        }
+    }
+
+    /// Checks `type_def` against one interface it implements: it must also
+    /// implement the interface's own interfaces and define each of its fields
+    /// with a compatible type and the same arguments.
+    fn check_implementation(&mut self, type_def: &TypeDef, interface: &TypeDef) {
+        for name in &interface.interfaces {
+            if *name != type_def.name && !type_def.interfaces.contains(name) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "missing-interface",
+                        format!(
+                            "'{}' must also implement '{}', which '{}' implements",
+                            type_def.name, name, interface.name
+                        ),
+                    )
+                    .with_primary(type_def.span, "")
+                    .with_secondary(interface.span, format!("'{}' defined here", interface.name)),
+                );
+            }
+        }
+
+        for expected in &interface.fields {
+            let Some(field) = type_def.fields.iter().find(|f| f.name == expected.name) else {
+                self.errors.push(
+                    Diagnostic::error(
+                        "missing-interface-field",
+                        format!(
+                            "'{}' must define field '{}' of interface '{}'",
+                            type_def.name, expected.name, interface.name
+                        ),
+                    )
+                    .with_primary(type_def.span, "")
+                    .with_secondary(expected.span, "required by this field"),
+                );
+                continue;
+            };
+            self.check_implemented_field(type_def, field, interface, expected);
+        }
+    }
+
+    fn check_implemented_field(
+        &mut self,
+        type_def: &TypeDef,
+        field: &Field,
+        interface: &TypeDef,
+        expected: &Field,
+    ) {
+        if !self.is_subtype(&field.field_type, &expected.field_type) {
+            self.errors.push(
+                Diagnostic::error(
+                    "invalid-interface-field",
+                    format!(
+                        "Field '{}.{}' must have type '{}' of interface '{}' or a subtype of it, \
+                         but has type '{}'",
+                        type_def.name,
+                        field.name,
+                        print_type_ref(&expected.field_type),
+                        interface.name,
+                        print_type_ref(&field.field_type)
+                    ),
+                )
+                .with_primary(field.span, "")
+                .with_secondary(expected.span, "interface field defined here"),
+            );
+        }
+
+        let arguments: &[InputValue] = field.arguments.as_deref().unwrap_or_default();
+        let expected_arguments: &[InputValue] = expected.arguments.as_deref().unwrap_or_default();
+
+        for expected_argument in expected_arguments {
+            match arguments.iter().find(|a| a.name == expected_argument.name) {
+                None => self.errors.push(
+                    Diagnostic::error(
+                        "missing-interface-argument",
+                        format!(
+                            "Field '{}.{}' must accept argument '{}' of interface '{}'",
+                            type_def.name, field.name, expected_argument.name, interface.name
+                        ),
+                    )
+                    .with_primary(field.span, "")
+                    .with_secondary(expected_argument.span, "required by this argument"),
+                ),
+                Some(argument) if argument.value_type != expected_argument.value_type => {
+                    self.errors.push(
+                        Diagnostic::error(
+                            "invalid-interface-argument",
+                            format!(
+                                "Argument '{}.{}({}:)' must have type '{}' of interface '{}', \
+                                 but has type '{}'",
+                                type_def.name,
+                                field.name,
+                                argument.name,
+                                print_type_ref(&expected_argument.value_type),
+                                interface.name,
+                                print_type_ref(&argument.value_type)
+                            ),
+                        )
+                        .with_primary(argument.span, "")
+                        .with_secondary(expected_argument.span, "interface argument defined here"),
+                    )
+                }
+                Some(_) => {}
+            }
+        }
+
+        for argument in arguments {
+            let required: bool = matches!(argument.value_type, TypeRef::NonNull(_))
+                && argument.default_value.is_none();
+            if required && !expected_arguments.iter().any(|a| a.name == argument.name) {
+                self.errors.push(
+                    Diagnostic::error(
+                        "required-interface-argument",
+                        format!(
+                            "Argument '{}.{}({}:)' must be optional, as interface '{}' does not \
+                             define it",
+                            type_def.name, field.name, argument.name, interface.name
+                        ),
+                    )
+                    .with_primary(argument.span, "")
+                    .with_help("make the argument nullable or give it a default value"),
+                );
+            }
+        }
+    }
+
+    /// Returns `true` if a field of type `sub` may implement an interface
+    /// field of type `sup`: the same type, a non-null or list-wise subtype of
+    /// it, or an object or interface that is a possible type of it.
+    fn is_subtype(&self, sub: &TypeRef, sup: &TypeRef) -> bool {
+        match (sub, sup) {
+            (TypeRef::NonNull(sub), TypeRef::NonNull(sup)) => self.is_subtype(sub, sup),
+            (TypeRef::NonNull(sub), sup) => self.is_subtype(sub, sup),
+            (_, TypeRef::NonNull(_)) => false,
+            (TypeRef::List(sub), TypeRef::List(sup)) => self.is_subtype(sub, sup),
+            (TypeRef::Named(sub), TypeRef::Named(sup)) => {
+                sub == sup
+                    || match (self.types.get(sub.as_str()), self.types.get(sup.as_str())) {
+                        (
+                            Some(Definition::Type(sub_def) | Definition::Interface(sub_def)),
+                            Some(Definition::Interface(_)),
+                        ) => sub_def.interfaces.contains(sup),
+                        (Some(Definition::Type(_)), Some(Definition::Union(union_def))) => {
+                            union_def
+                                .members
+                                .iter()
+                                .any(|member| member.named_type() == sub)
+                        }
+                        _ => false,
+                    }
+            }
+            _ => false,
+        }
+    }
+
+    /// Reports input types that contain themselves through non-null fields,
+    /// for which no finite value exists.
+    fn check_input_cycles(&mut self, document: &'a Document) {
+        let mut visited: HashSet<&str> = HashSet::new();
+
+        for definition in &document.definitions {
+            if let Definition::Input(input) = definition {
+                let mut path: Vec<(&str, &InputValue)> = Vec::new();
+                self.visit_input(input, &mut visited, &mut path);
+            }
+        }
+    }
+
+    fn visit_input(
+        &mut self,
+        input: &'a InputObjectDef,
+        visited: &mut HashSet<&'a str>,
+        path: &mut Vec<(&'a str, &'a InputValue)>,
+    ) {
+        if !visited.insert(&input.name) {
+            return;
+        }
+
+        for field in &input.fields {
+            let TypeRef::NonNull(inner) = &field.value_type else {
+                continue;
+            };
+            let TypeRef::Named(name) = inner.as_ref() else {
+                continue;
+            };
+            let Some(Definition::Input(target)) = self.types.get(name.as_str()).copied() else {
+                continue;
+            };
+
+            path.push((&input.name, field));
+            match path.iter().position(|(owner, _)| owner == name) {
+                Some(start) => {
+                    let cycle: Vec<String> = path[start..]
+                        .iter()
+                        .map(|(owner, field)| format!("{}.{}", owner, field.name))
+                        .collect();
+                    self.errors.push(
+                        Diagnostic::error(
+                            "input-cycle",
+                            format!(
+                                "Input type '{}' references itself through non-null fields: {}",
+                                name,
+                                cycle.join(" -> ")
+                            ),
+                        )
+                        .with_primary(path[start].1.span, "")
+                        .with_help("make one of the fields nullable or a list"),
+                    );
+                }
+                None => self.visit_input(target, visited, path),
+            }
+            path.pop();
+        }
+    }
+}
+
+/// Describes the first way `directive` differs from the `built_in` directive
+/// of the same name, if any. Fewer locations and fewer optional arguments
+/// are fine.
+fn directive_mismatch(directive: &DirectiveDef, built_in: &DirectiveDef) -> Option<String> {
+    let arguments: &[InputValue] = directive.arguments.as_deref().unwrap_or_default();
+    let built_in_arguments: &[InputValue] = built_in.arguments.as_deref().unwrap_or_default();
+
+    for argument in arguments {
+        match built_in_arguments.iter().find(|a| a.name == argument.name) {
+            None => return Some(format!("unknown argument '{}'", argument.name)),
+            Some(expected) if expected.value_type != argument.value_type => {
+                return Some(format!(
+                    "argument '{}' must have type '{}'",
+                    argument.name,
+                    print_type_ref(&expected.value_type)
+                ))
+            }
+            Some(_) => {}
+        }
+    }
+    for expected in built_in_arguments {
+        let required: bool =
+            matches!(expected.value_type, TypeRef::NonNull(_)) && expected.default_value.is_none();
+        if required && !arguments.iter().any(|a| a.name == expected.name) {
+            return Some(format!("missing argument '{}'", expected.name));
+        }
+    }
+    if let Some(location) = directive
+        .locations
+        .iter()
+        .find(|location| !built_in.locations.contains(location))
+    {
+        return Some(format!("cannot be used on {}", location.as_str()));
+    }
+    if directive.repeatable && !built_in.repeatable {
+        return Some("must not be repeatable".to_string());
+    }
+    None
+}
+
+/// Describes the kind of a definition for error messages, e.g. "an input
+/// type".
+fn describe(definition: &Definition) -> &'static str {
+    match definition {
+        Definition::Type(_) => "an object type",
+        Definition::Input(_) => "an input type",
+        Definition::Interface(_) => "an interface",
+        Definition::Scalar(_) => "a scalar",
+        Definition::Union(_) => "a union",
+        Definition::Enum(_) => "an enum",
+        Definition::Schema(_) => "a schema",
+        Definition::Directive(_) => "a directive",
+        Definition::Extension(_) => "an extension",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::common::parser::Parser;
+    use crate::core::merge::merge_extensions;
+    use crate::core::parse::parse_document;
+
+    fn validate(source: &str) -> Vec<Diagnostic> {
+        let document: Document = parse_document(&mut Parser::new(source))
+            .into_result()
+            .unwrap();
+        validate_document(&merge_extensions(document).into_result().unwrap())
+    }
+
+    fn codes(errors: &[Diagnostic]) -> Vec<&str> {
+        errors.iter().map(|e| e.code.as_str()).collect()
+    }
+
+    #[test]
+    fn test_valid_schema() {
+        let errors: Vec<Diagnostic> = validate(
+            r#"
+            schema { query: Query }
+            directive @auth(role: Role = ADMIN) on FIELD_DEFINITION
+            interface Node { id: ID! }
+            interface Entity implements Node { id: ID! name: String }
+            type User implements Entity & Node { id: ID! name: String! friends(first: Int): [User!]! }
+            type Query { node(id: ID!): Node search: [Result] user(filter: Filter): User @auth }
+            union Result = User
+            enum Role { ADMIN USER }
+            input Filter { name: String next: Filter role: Role! }
+            extend type Query { me: User }
+            "#,
+        );
+
+        assert!(errors.is_empty(), "Expected no errors, got {:?}", errors);
+    }
+
+    #[test]
+    fn test_duplicate_definitions() {
+        let errors: Vec<Diagnostic> = validate(
+            "type A { a: Int a: String }\nscalar A\nenum E { X X }\ntype Q { f(a: Int, a: Int): Int }\nschema { query: Q }\nschema { query: Q query: Q }",
+        );
+
+        assert_eq!(
+            codes(&errors),
+            vec![
+                "duplicate-field",
+                "duplicate-definition",
+                "duplicate-enum-value",
+                "duplicate-argument",
+                "duplicate-definition",
+                "duplicate-root-type",
+            ]
+        );
+        assert_eq!(errors[1].message, "Type 'A' is defined more than once");
+        assert_eq!(errors[0].message, "Field 'A.a' is defined more than once");
+    }
+
+    #[test]
+    fn test_unknown_and_misplaced_types() {
+        let errors: Vec<Diagnostic> = validate(
+            "type User { address: Address input: AddressInput }\ninput AddressInput { city: String user: User }\ntype Query { users(where: User): [User] }",
+        );
+
+        assert_eq!(
+            codes(&errors),
+            vec![
+                "unknown-type",
+                "invalid-output-type",
+                "invalid-input-type",
+                "invalid-input-type"
+            ]
+        );
+        assert_eq!(errors[0].message, "Unknown type 'Address'");
+        assert_eq!(
+            errors[1].message,
+            "Field 'User.input' must have an output type, but 'AddressInput' is an input type"
+        );
+        assert_eq!(
+            errors[3].message,
+            "Argument 'Query.users(where:)' must have an input type, but 'User' is an object type"
+        );
+    }
+
+    #[test]
+    fn test_union_members_must_be_objects() {
+        let errors: Vec<Diagnostic> = validate(
+            "interface Node { id: ID }\nunion U = Node | String | Missing | Node\ntype A { u: U }",
+        );
+
+        assert_eq!(
+            codes(&errors),
+            vec![
+                "invalid-union-member",
+                "invalid-union-member",
+                "unknown-type",
+                "duplicate-union-member"
+            ]
+        );
+        assert_eq!(
+            errors[0].message,
+            "Member 'Node' of union 'U' must be an object type, but it is an interface"
+        );
+    }
+
+    #[test]
+    fn test_reserved_names_and_enum_values() {
+        let errors: Vec<Diagnostic> = validate("type __Secret { __id: ID }\nenum E { A }");
+        assert_eq!(codes(&errors), vec!["reserved-name", "reserved-name"]);
+        assert_eq!(
+            errors[0].message,
+            "Name '__Secret' must not begin with '__'"
+        );
+
+        let mut document: Document = parse_document(&mut Parser::new("enum E { A B }"))
+            .into_result()
+            .unwrap();
+        if let Definition::Enum(enum_def) = &mut document.definitions[0] {
+            enum_def.values[0].name = "true".into();
+            enum_def.values[1].name = "null".into();
+        }
+        let errors: Vec<Diagnostic> = validate_document(&document);
+        assert_eq!(
+            codes(&errors),
+            vec!["invalid-enum-value", "invalid-enum-value"]
+        );
+        assert_eq!(errors[1].message, "Enum value 'E.null' is reserved");
+    }
+
+    #[test]
+    fn test_interface_implementations() {
+        let errors: Vec<Diagnostic> = validate(
+            r#"
+            interface Node { id: ID! }
+            interface Named implements Node { id: ID! name(locale: String): String }
+            type User implements Named { id: ID name(locale: Int, strict: Boolean!): String }
+            type Post implements Named & Node { id: ID! }
+            type Tag implements User { id: ID! }
+            "#,
+        );
+
+        assert_eq!(
+            codes(&errors),
+            vec![
+                "missing-interface",
+                "invalid-interface-field",
+                "invalid-interface-argument",
+                "required-interface-argument",
+                "missing-interface-field",
+                "invalid-implementation",
+            ]
+        );
+        assert_eq!(
+            errors[0].message,
+            "'User' must also implement 'Node', which 'Named' implements"
+        );
+        assert_eq!(
+            errors[1].message,
+            "Field 'User.id' must have type 'ID!' of interface 'Named' or a subtype of it, but has type 'ID'"
+        );
+        assert_eq!(
+            errors[4].message,
+            "'Post' must define field 'name' of interface 'Named'"
+        );
+    }
+
+    #[test]
+    fn test_covariant_interface_fields() {
+        let errors: Vec<Diagnostic> = validate(
+            r#"
+            interface Node { id: ID! parent: Node children: [Node] result: Result }
+            type User implements Node { id: ID! parent: User! children: [User!]! result: User }
+            union Result = User
+            "#,
+        );
+
+        assert!(errors.is_empty(), "Expected no errors, got {:?}", errors);
+    }
+
+    #[test]
+    fn test_root_types() {
+        let errors: Vec<Diagnostic> =
+            validate("schema { query: Query mutation: Mutation }\ninput Query { a: Int }");
+
+        assert_eq!(codes(&errors), vec!["invalid-root-type", "unknown-type"]);
+        assert_eq!(
+            errors[0].message,
+            "The query root type must be an object type, but 'Query' is an input type"
+        );
+    }
+
+    #[test]
+    fn test_input_cycles() {
+        let errors: Vec<Diagnostic> = validate(
+            "input A { b: B! }\ninput B { a: A! c: C }\ninput C { c: C! }\ninput D { d: [D!]! }",
+        );
+
+        assert_eq!(codes(&errors), vec!["input-cycle", "input-cycle"]);
+        assert_eq!(
+            errors[0].message,
+            "Input type 'A' references itself through non-null fields: A.b -> B.a"
+        );
+        assert_eq!(
+            errors[1].message,
+            "Input type 'C' references itself through non-null fields: C.c"
+        );
+    }
+
+    #[test]
+    fn test_built_in_redefinitions() {
+        let errors: Vec<Diagnostic> = validate(
+            r#"
+            scalar String
+            directive @deprecated(reason: String = "Gone") on FIELD_DEFINITION | ENUM_VALUE
+            type Boolean { value: Int }
+            directive @skip(if: Boolean) on FIELD
+            directive @include on FIELD
+            directive @specifiedBy(url: String!) repeatable on SCALAR | OBJECT
+            directive @oneOf(strict: Boolean) on INPUT_OBJECT
+            "#,
+        );
+
+        assert_eq!(codes(&errors), vec!["incompatible-built-in"; 5]);
+        assert_eq!(
+            errors[0].message,
+            "'Boolean' is a built-in scalar and cannot be redefined as an object type"
+        );
+        let labels: Vec<&str> = errors[1..]
+            .iter()
+            .map(|e| e.primary.as_ref().unwrap().message.as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            vec![
+                "argument 'if' must have type 'Boolean!'",
+                "missing argument 'if'",
+                "cannot be used on OBJECT",
+                "unknown argument 'strict'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_errors_are_sorted_by_position() {
+        let errors: Vec<Diagnostic> =
+            validate("type B { a: Missing }\nunion U = Query\ntype A { b: Other }");
+
+        let offsets: Vec<usize> = errors
+            .iter()
+            .map(|e| e.span().unwrap().start.offset)
+            .collect();
+        let mut sorted: Vec<usize> = offsets.clone();
+        sorted.sort();
+        assert_eq!(offsets, sorted);
+        assert_eq!(errors.len(), 3);
+    }
+}
@@ -1 +1,4 @@
+// Diagnostics are only built on the error path, so their size does not matter.
+#![allow(clippy::result_large_err)]
+
 pub mod core;
@@ -1,8 +1,9 @@
-use clap::{arg, command, Parser};
-use clap_derive::Parser;
+use clap::Parser;
+use clap_derive::{Parser, Subcommand};
 
 use std::fs::{self, File};
 use std::io::{BufWriter, Write};
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 use std::time::Instant;
 
@@ -11,7 +12,12 @@ use log::{error, info};
 mod config;
 use config::config::CONFIG;
 
-use graphqlgen::core::parse::{generate_tokens, parse_document};
+use graphqlgen::core::common::error::Diagnostic;
+use graphqlgen::core::common::parser::Parser as GraphQLParser;
+use graphqlgen::core::format::format_schema;
+use graphqlgen::core::merge::merge_extensions;
+use graphqlgen::core::parse::parse_document;
+use graphqlgen::core::validate::validate_document;
 
 #[derive(Debug, Parser)]
 #[command(name = "graphqlgen")]
@@ -19,6 +25,26 @@ use graphqlgen::core::parse::{generate_tokens, parse_document};
 struct Cli {
     #[arg(short, long)]
     plugin: Option<String>,
+
+    /// Include source spans (line, column, byte offset) in the AST sent to the plugin
+    #[arg(long)]
+    spans: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    /// Rewrite schema files in the canonical style, keeping `#` comments
+    Fmt {
+        /// Schema files to format, the configured schema by default
+        files: Vec<PathBuf>,
+
+        /// Only report files that are not formatted, exiting with an error if there are any
+        #[arg(long)]
+        check: bool,
+    },
 }
 
 fn main() {
@@ -42,6 +68,15 @@ fn main() {
     let start = Instant::now();
     let args = Cli::parse();
 
+    if let Some(Commands::Fmt { files, check }) = args.command {
+        let files: Vec<PathBuf> = if files.is_empty() {
+            vec![PathBuf::from(&CONFIG.schema)]
+        } else {
+            files
+        };
+        fmt(&files, check);
+    }
+
     let plugin = args.plugin.as_deref().unwrap_or(&CONFIG.plugin);
     if plugin.is_empty() {
         error!("Error: No plugin specified via CLI or config.");
@@ -73,23 +108,20 @@ fn main() {
         }
     };
 
-    let tokens = match generate_tokens(&schema_content) {
-        Ok(tokens) => tokens,
-        Err(e) => {
-            error!("Error: Failed to generate tokens: {}", e);
-            std::process::exit(1);
-        }
-    };
+    let parsed = parse_document(&mut GraphQLParser::new(&schema_content));
+    let merged = merge_extensions(parsed.output);
 
-    {
-        let file: File = File::create("tokens.txt").expect("Failed to create tokens.txt");
-        let mut writer: BufWriter<File> = BufWriter::new(file);
-        for token in &tokens {
-            writeln!(writer, "{:?}", token).expect("Write failed");
-        }
+    let mut diagnostics: Vec<Diagnostic> = parsed.errors;
+    diagnostics.extend(merged.errors);
+    if !diagnostics.is_empty() {
+        report_and_exit(&diagnostics, &schema_content);
     }
+    let parsed_schema = merged.output;
 
-    let parsed_schema = parse_document(tokens).expect("Failed to parse schema");
+    let diagnostics: Vec<Diagnostic> = validate_document(&parsed_schema);
+    if !diagnostics.is_empty() {
+        report_and_exit(&diagnostics, &schema_content);
+    }
 
     {
         let file: File = File::create("test.txt").expect("Failed to create test.txt");
@@ -112,7 +144,12 @@ fn main() {
 
         writeln!(stdin, "{}", output_path.display()).expect("Failed to write output path");
 
-        let json = serde_json::to_string(&parsed_schema).expect("Failed to serialize schema");
+        let mut ast = serde_json::to_value(&parsed_schema).expect("Failed to serialize schema");
+        if !args.spans {
+            strip_spans(&mut ast);
+        }
+
+        let json = serde_json::to_string(&ast).expect("Failed to serialize schema");
         writeln!(stdin, "{}", json).expect("Failed to write AST");
     }
 
@@ -125,3 +162,82 @@ fn main() {
         std::process::exit(1);
     }
 }
+
+/// Prints each diagnostic as an annotated excerpt of the schema and exits.
+fn report_and_exit(diagnostics: &[Diagnostic], source: &str) -> ! {
+    for diagnostic in diagnostics {
+        eprintln!("{}", diagnostic.render(source, &CONFIG.schema));
+    }
+
+    let count: usize = diagnostics.iter().filter(|d| d.is_error()).count();
+    error!(
+        "Aborting due to {} error{}",
+        count,
+        if count == 1 { "" } else { "s" }
+    );
+    std::process::exit(1);
+}
+
+/// Formats each of `files` in place, or with `check` only reports the ones
+/// that are not formatted. Exits with an error if any file could not be read
+/// or parsed, or with `check` if any file is not formatted.
+fn fmt(files: &[PathBuf], check: bool) -> ! {
+    let mut failed: bool = false;
+
+    for path in files {
+        let source: String = match fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(e) => {
+                error!(
+                    "Error: Failed to read schema file '{}': {}",
+                    path.display(),
+                    e
+                );
+                failed = true;
+                continue;
+            }
+        };
+
+        let formatted: String = match format_schema(&source) {
+            Ok(formatted) => formatted,
+            Err(diagnostics) => {
+                for diagnostic in &diagnostics {
+                    eprintln!(
+                        "{}",
+                        diagnostic.render(&source, &path.display().to_string())
+                    );
+                }
+                failed = true;
+                continue;
+            }
+        };
+
+        if formatted == source {
+            continue;
+        }
+        if check {
+            error!("{} is not formatted", path.display());
+            failed = true;
+        } else if let Err(e) = fs::write(path, formatted) {
+            error!("Error: Failed to write '{}': {}", path.display(), e);
+            failed = true;
+        } else {
+            info!("Formatted {}", path.display());
+        }
+    }
+
+    std::process::exit(if failed { 1 } else { 0 });
+}
+
+/// Removes every `span` entry from the serialized AST. Plugins deserialize
+/// missing spans as their default value.
+fn strip_spans(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.remove("span");
+            map.values_mut().for_each(strip_spans);
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(strip_spans),
+        _ => {}
+    }
+}
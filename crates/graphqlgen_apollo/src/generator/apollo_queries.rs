@@ -1,9 +1,11 @@
-use graphqlgen_schema::ast::{Definition, Document, Field, TypeDef, TypeRef};
+use graphqlgen_schema::ast::{Field, OperationType};
+use graphqlgen_schema::printer::{print_type_ref, print_value};
+use graphqlgen_schema::schema::Schema;
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
 
-pub fn generate_apollo_queries(output_path: &str, ast: &Document) -> Result<(), String> {
+pub fn generate_apollo_queries(output_path: &str, schema: &Schema) -> Result<(), String> {
     let file_path = Path::new(output_path).join("queries.ts");
 
     let mut file =
@@ -14,39 +16,14 @@ pub fn generate_apollo_queries(output_path: &str, ast: &Document) -> Result<(),
 
     let mut gql_vars: Vec<String> = Vec::new();
 
-    for def in &ast.definitions {
-        if let Definition::Type(TypeDef {
-            name,
-            fields,
-            directives: _,
-            description: _,
-        }) = def
-        {
-            if name == "Query" || name == "Mutation" {
-                for field in fields {
-                    let gql_var_name: String =
-                        format!("{}_{}", name.to_lowercase(), field.name).to_uppercase();
-
-                    let operation_type: String = name.to_lowercase(); // query or mutation
-                    let (params_str, param_values) = render_params(&field);
-
-                    let query_string: String = format!(
-                        "const {} = gql`\n  {} {}{} {{\n    {}{} {{\n      {}\n    }}\n  }}\n`;\n\n",
-                        gql_var_name,
-                        operation_type,
-                        field.name,
-                        params_str,
-                        name,
-                        param_values,
-                        "id" // Default field output (you can later infer this better)
-                    );
-
-                    file.write_all(query_string.as_bytes())
-                        .map_err(|e| format!("Failed to write {}: {}", gql_var_name, e))?;
-
-                    gql_vars.push(gql_var_name);
-                }
-            }
+    for (operation, root) in schema.root_operation_types() {
+        for field in &root.fields {
+            let gql_var_name: String = gql_var_name(operation, field);
+
+            file.write_all(render_query(operation, field).as_bytes())
+                .map_err(|e| format!("Failed to write {}: {}", gql_var_name, e))?;
+
+            gql_vars.push(gql_var_name);
         }
     }
 
@@ -58,11 +35,41 @@ pub fn generate_apollo_queries(output_path: &str, ast: &Document) -> Result<(),
     Ok(())
 }
 
-fn render_params(field: &Field) -> (String, String) {
-    if let TypeRef::NonNull(_) | TypeRef::Named(_) | TypeRef::List(_) = &field.field_type {
-        // If there are no args (should be handled above), return empty
+fn gql_var_name(operation: OperationType, field: &Field) -> String {
+    format!("{}_{}", operation.as_str(), field.name).to_uppercase()
+}
+
+/// Renders the `gql` constant for one root field. The operation selects the
+/// field itself, e.g. `query user($id: ID!) { user(id: $id) { id } }`.
+fn render_query(operation: OperationType, field: &Field) -> String {
+    let operation_type: &str = operation.as_str(); // query, mutation or subscription
+    let (params_str, param_values) = render_params(field);
+
+    let mut query_string: String = String::new();
+    if let Some(deprecation) = &field.deprecation {
+        let reason: String = deprecation
+            .reason
+            .lines()
+            .collect::<Vec<&str>>()
+            .join(" ")
+            .replace("*/", "*\\/");
+        query_string.push_str(&format!("/** @deprecated {} */\n", reason));
     }
 
+    query_string.push_str(&format!(
+        "const {} = gql`\n  {} {}{} {{\n    {}{} {{\n      {}\n    }}\n  }}\n`;\n\n",
+        gql_var_name(operation, field),
+        operation_type,
+        field.name,
+        params_str,
+        field.name,
+        param_values,
+        "id" // Default field output (you can later infer this better)
+    ));
+    query_string
+}
+
+fn render_params(field: &Field) -> (String, String) {
     // Let's assume a simple argument list for now based on field.name
     // For example: Tweet(id: ID!) or Tweets(limit: Int, skip: Int)
     let mut var_declarations = vec![];
@@ -70,12 +77,17 @@ fn render_params(field: &Field) -> (String, String) {
 
     if let Some(args) = &field.arguments {
         for arg in args {
-            let var_type = match &arg.value_type {
-                TypeRef::NonNull(inner) => format!("{}!", type_ref_to_str(inner)),
-                other => type_ref_to_str(other),
-            };
-
-            var_declarations.push(format!("${}: {}", arg.name, var_type));
+            let var_type: String = print_type_ref(&arg.value_type);
+
+            match &arg.default_value {
+                Some(default) => var_declarations.push(format!(
+                    "${}: {} = {}",
+                    arg.name,
+                    var_type,
+                    escape_template_literal(&print_value(default))
+                )),
+                None => var_declarations.push(format!("${}: {}", arg.name, var_type)),
+            }
             var_usages.push(format!("{}: ${}", arg.name, arg.name));
         }
     }
@@ -90,10 +102,69 @@ fn render_params(field: &Field) -> (String, String) {
     }
 }
 
-fn type_ref_to_str(t: &TypeRef) -> String {
-    match t {
-        TypeRef::Named(name) => name.clone(),
-        TypeRef::NonNull(inner) => format!("{}!", type_ref_to_str(inner)),
-        TypeRef::List(inner) => format!("[{}]", type_ref_to_str(inner)),
+/// Escapes GraphQL text for a JavaScript template literal, where `gql`
+/// would otherwise read the escapes of printed strings as its own.
+fn escape_template_literal(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('`', "\\`")
+        .replace("${", "\\${")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphqlgen_schema::ast::{Deprecation, TypeRef, Value};
+    use graphqlgen_schema::test_utils::{field, input_value, named};
+
+    #[test]
+    fn test_render_query_selects_root_field() {
+        let mut user = field("user", named("User"));
+        user.arguments = Some(vec![input_value(
+            "id",
+            TypeRef::NonNull(Box::new(named("ID"))),
+            None,
+        )]);
+
+        assert_eq!(
+            render_query(OperationType::Query, &user),
+            "const QUERY_USER = gql`\n  query user($id: ID!) {\n    user(id: $id) {\n      id\n    }\n  }\n`;\n\n"
+        );
+    }
+
+    #[test]
+    fn test_render_params_escapes_defaults() {
+        let query = input_value(
+            "query",
+            named("String"),
+            Some(Value::String("a\nb `c`".to_string())),
+        );
+        let filter = input_value(
+            "filter",
+            TypeRef::List(Box::new(named("Float"))),
+            Some(Value::List(vec![Value::Float(1.0)])),
+        );
+
+        let mut search = field("search", named("User"));
+        search.arguments = Some(vec![query, filter]);
+
+        let (params, values) = render_params(&search);
+        assert_eq!(
+            params,
+            r#"($query: String = "a\\nb \`c\`", $filter: [Float] = [1.0])"#
+        );
+        assert_eq!(values, "(query: $query, filter: $filter)");
+    }
+
+    #[test]
+    fn test_render_deprecated_mutation() {
+        let mut logout = field("logout", named("User"));
+        logout.deprecation = Some(Deprecation {
+            reason: "Use */signOut".to_string(),
+        });
+
+        assert_eq!(
+            render_query(OperationType::Mutation, &logout),
+            "/** @deprecated Use *\\/signOut */\nconst MUTATION_LOGOUT = gql`\n  mutation logout {\n    logout {\n      id\n    }\n  }\n`;\n\n"
+        );
     }
 }
@@ -3,6 +3,7 @@ use std::fs;
 use std::io::{self, BufRead, Read};
 
 use graphqlgen_schema::ast::Document;
+use graphqlgen_schema::schema::Schema;
 
 mod generator;
 
@@ -39,6 +40,6 @@ fn main() {
     generator::apollo_config::generate_apollo_config(schema.source.clone())
         .expect("Failed to generate Apollo config");
 
-    generator::apollo_queries::generate_apollo_queries(&schema.source, &schema.ast)
+    generator::apollo_queries::generate_apollo_queries(&schema.source, &Schema::new(&schema.ast))
         .expect("Failed to generate Apollo queries");
 }
@@ -1,18 +1,440 @@
 use serde::{Deserialize, Serialize};
 
+/// A location in the source text. `line` and `column` are 1-based, `offset`
+/// is the byte offset from the start of the input. The default value (all
+/// zeroes) marks a node that did not come from source text.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pos {
+    pub line: usize,
+    pub column: usize,
+    pub offset: usize,
+}
+
+/// The half-open source range `[start, end)` a token or node was parsed from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq, Eq)]
+pub struct Span {
+    pub start: Pos,
+    pub end: Pos,
+}
+
+impl Span {
+    pub fn new(start: Pos, end: Pos) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns `true` for spans that were not produced from source text.
+    pub fn is_dummy(&self) -> bool {
+        self.start.line == 0
+    }
+
+    /// The smallest span covering both `self` and `other`.
+    pub fn to(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+/// A `#` comment. Comments are only kept when the parser is asked to keep
+/// them, and are then attached to the node they belong to.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Comment {
+    /// The text after the `#`.
+    pub text: String,
+    /// Whether a blank line separates the comment from what follows it, as
+    /// below a license header.
+    #[serde(default)]
+    pub blank_line_after: bool,
+    #[serde(default)]
+    pub span: Span,
+}
+
+/// The comments attached to a node.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Comments {
+    /// Comments on the lines above the node and its description.
+    #[serde(default)]
+    pub leading: Vec<Comment>,
+    /// A comment at the end of the node's last line.
+    #[serde(default)]
+    pub trailing: Option<Comment>,
+    /// Comments after the last member of a type, enum or schema, before its
+    /// closing brace.
+    #[serde(default)]
+    pub inner: Vec<Comment>,
+}
+
+impl Comments {
+    pub fn is_empty(&self) -> bool {
+        self.leading.is_empty() && self.trailing.is_none() && self.inner.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Document {
     pub definitions: Vec<Definition>,
+    /// Comments after the last definition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
+}
+
+impl Document {
+    /// Resolves the object type name used for `operation`.
+    ///
+    /// Uses the `schema { ... }` definition when the document has one.
+    /// Otherwise falls back to the spec defaults (`Query`, `Mutation`,
+    /// `Subscription`), provided an object type with that name exists.
+    pub fn root_operation_type(&self, operation: OperationType) -> Option<&str> {
+        let schema: Option<&SchemaDef> = self.definitions.iter().find_map(|def| match def {
+            Definition::Schema(schema) => Some(schema),
+            _ => None,
+        });
+
+        match schema {
+            Some(schema) => schema
+                .operation_types
+                .iter()
+                .find(|root| root.operation == operation)
+                .map(|root| root.type_name.as_str()),
+            None => self.definitions.iter().find_map(|def| match def {
+                Definition::Type(type_def) if type_def.name == operation.default_type_name() => {
+                    Some(type_def.name.as_str())
+                }
+                _ => None,
+            }),
+        }
+    }
+
+    /// The root operation types of the document, see [`Document::root_operation_type`].
+    pub fn root_operation_types(&self) -> Vec<(OperationType, &str)> {
+        OperationType::ALL
+            .iter()
+            .filter_map(|&operation| {
+                self.root_operation_type(operation)
+                    .map(|name| (operation, name))
+            })
+            .collect()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Definition {
     Type(TypeDef),
     Scalar(ScalarDef),
-    Input(TypeDef),
+    Input(InputObjectDef),
     Interface(TypeDef),
     Union(UnionDef),
     Enum(EnumDef),
+    Schema(SchemaDef),
+    Directive(DirectiveDef),
+    Extension(Extension),
+}
+
+impl Definition {
+    /// The name of the defined type, `None` for schema definitions,
+    /// directive definitions and extensions.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Definition::Type(def) | Definition::Interface(def) => Some(&def.name),
+            Definition::Input(def) => Some(&def.name),
+            Definition::Scalar(def) => Some(&def.name),
+            Definition::Union(def) => Some(&def.name),
+            Definition::Enum(def) => Some(&def.name),
+            Definition::Schema(_) | Definition::Directive(_) | Definition::Extension(_) => None,
+        }
+    }
+
+    /// Returns `true` for the built-in scalars and directives from
+    /// [`crate::built_ins`].
+    pub fn is_built_in(&self) -> bool {
+        match self {
+            Definition::Scalar(def) => def.built_in,
+            Definition::Directive(def) => def.built_in,
+            _ => false,
+        }
+    }
+
+    /// The keyword the definition starts with.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Definition::Type(_) => "type",
+            Definition::Scalar(_) => "scalar",
+            Definition::Input(_) => "input",
+            Definition::Interface(_) => "interface",
+            Definition::Union(_) => "union",
+            Definition::Enum(_) => "enum",
+            Definition::Schema(_) => "schema",
+            Definition::Directive(_) => "directive",
+            Definition::Extension(_) => "extend",
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Definition::Type(def) | Definition::Interface(def) => def.span,
+            Definition::Input(def) => def.span,
+            Definition::Scalar(def) => def.span,
+            Definition::Union(def) => def.span,
+            Definition::Enum(def) => def.span,
+            Definition::Schema(def) => def.span,
+            Definition::Directive(def) => def.span,
+            Definition::Extension(ext) => ext.span(),
+        }
+    }
+
+    pub fn comments(&self) -> &Comments {
+        match self {
+            Definition::Type(def) | Definition::Interface(def) => &def.comments,
+            Definition::Input(def) => &def.comments,
+            Definition::Scalar(def) => &def.comments,
+            Definition::Union(def) => &def.comments,
+            Definition::Enum(def) => &def.comments,
+            Definition::Schema(def) => &def.comments,
+            Definition::Directive(def) => &def.comments,
+            Definition::Extension(ext) => ext.comments(),
+        }
+    }
+}
+
+/// An `extend ...` definition. It holds only what the extension adds to the
+/// extended definition, and never has a description.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Extension {
+    Type(TypeDef),
+    Scalar(ScalarDef),
+    Input(InputObjectDef),
+    Interface(TypeDef),
+    Union(UnionDef),
+    Enum(EnumDef),
+    Schema(SchemaDef),
+}
+
+impl Extension {
+    /// The name of the extended definition, `None` for `extend schema`.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Extension::Type(ext) | Extension::Interface(ext) => Some(&ext.name),
+            Extension::Input(ext) => Some(&ext.name),
+            Extension::Scalar(ext) => Some(&ext.name),
+            Extension::Union(ext) => Some(&ext.name),
+            Extension::Enum(ext) => Some(&ext.name),
+            Extension::Schema(_) => None,
+        }
+    }
+
+    /// The keyword following `extend`.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Extension::Type(_) => "type",
+            Extension::Scalar(_) => "scalar",
+            Extension::Input(_) => "input",
+            Extension::Interface(_) => "interface",
+            Extension::Union(_) => "union",
+            Extension::Enum(_) => "enum",
+            Extension::Schema(_) => "schema",
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Extension::Type(ext) | Extension::Interface(ext) => ext.span,
+            Extension::Input(ext) => ext.span,
+            Extension::Scalar(ext) => ext.span,
+            Extension::Union(ext) => ext.span,
+            Extension::Enum(ext) => ext.span,
+            Extension::Schema(ext) => ext.span,
+        }
+    }
+
+    pub fn comments(&self) -> &Comments {
+        match self {
+            Extension::Type(ext) | Extension::Interface(ext) => &ext.comments,
+            Extension::Input(ext) => &ext.comments,
+            Extension::Scalar(ext) => &ext.comments,
+            Extension::Union(ext) => &ext.comments,
+            Extension::Enum(ext) => &ext.comments,
+            Extension::Schema(ext) => &ext.comments,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub enum OperationType {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl OperationType {
+    pub const ALL: [OperationType; 3] = [
+        OperationType::Query,
+        OperationType::Mutation,
+        OperationType::Subscription,
+    ];
+
+    /// The keyword used for the operation in GraphQL source.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+            OperationType::Subscription => "subscription",
+        }
+    }
+
+    /// The root type name assumed when a schema has no `schema` definition.
+    pub fn default_type_name(&self) -> &'static str {
+        match self {
+            OperationType::Query => "Query",
+            OperationType::Mutation => "Mutation",
+            OperationType::Subscription => "Subscription",
+        }
+    }
+
+    pub fn from_keyword(keyword: &str) -> Option<Self> {
+        OperationType::ALL
+            .into_iter()
+            .find(|operation| operation.as_str() == keyword)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaDef {
+    pub operation_types: Vec<RootOperationTypeDef>,
+    pub directives: Option<Vec<Directive>>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
+}
+
+/// One `query: Query` entry of a schema definition.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RootOperationTypeDef {
+    pub operation: OperationType,
+    pub type_name: String,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
+}
+
+/// `directive @name(arguments) repeatable on LOCATION | LOCATION`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DirectiveDef {
+    pub name: String,
+    pub arguments: Option<Vec<InputValue>>,
+    pub repeatable: bool,
+    pub locations: Vec<DirectiveLocation>,
+    pub description: Option<String>,
+    /// Set on the spec's built-in directives from [`crate::built_ins`],
+    /// which printers omit.
+    #[serde(default)]
+    pub built_in: bool,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
+}
+
+/// Where a directive may be applied. Variants are ordered as the spec lists
+/// them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DirectiveLocation {
+    // Executable locations
+    Query,
+    Mutation,
+    Subscription,
+    Field,
+    FragmentDefinition,
+    FragmentSpread,
+    InlineFragment,
+    VariableDefinition,
+
+    // Type system locations
+    Schema,
+    Scalar,
+    Object,
+    FieldDefinition,
+    ArgumentDefinition,
+    Interface,
+    Union,
+    Enum,
+    EnumValue,
+    InputObject,
+    InputFieldDefinition,
+}
+
+impl DirectiveLocation {
+    pub const ALL: [DirectiveLocation; 19] = [
+        DirectiveLocation::Query,
+        DirectiveLocation::Mutation,
+        DirectiveLocation::Subscription,
+        DirectiveLocation::Field,
+        DirectiveLocation::FragmentDefinition,
+        DirectiveLocation::FragmentSpread,
+        DirectiveLocation::InlineFragment,
+        DirectiveLocation::VariableDefinition,
+        DirectiveLocation::Schema,
+        DirectiveLocation::Scalar,
+        DirectiveLocation::Object,
+        DirectiveLocation::FieldDefinition,
+        DirectiveLocation::ArgumentDefinition,
+        DirectiveLocation::Interface,
+        DirectiveLocation::Union,
+        DirectiveLocation::Enum,
+        DirectiveLocation::EnumValue,
+        DirectiveLocation::InputObject,
+        DirectiveLocation::InputFieldDefinition,
+    ];
+
+    /// The name used for the location in GraphQL source, e.g. `FIELD_DEFINITION`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DirectiveLocation::Query => "QUERY",
+            DirectiveLocation::Mutation => "MUTATION",
+            DirectiveLocation::Subscription => "SUBSCRIPTION",
+            DirectiveLocation::Field => "FIELD",
+            DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+            DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+            DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+            DirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
+            DirectiveLocation::Schema => "SCHEMA",
+            DirectiveLocation::Scalar => "SCALAR",
+            DirectiveLocation::Object => "OBJECT",
+            DirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
+            DirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
+            DirectiveLocation::Interface => "INTERFACE",
+            DirectiveLocation::Union => "UNION",
+            DirectiveLocation::Enum => "ENUM",
+            DirectiveLocation::EnumValue => "ENUM_VALUE",
+            DirectiveLocation::InputObject => "INPUT_OBJECT",
+            DirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        DirectiveLocation::ALL
+            .into_iter()
+            .find(|location| location.as_str() == name)
+    }
+
+    /// Returns `true` for locations in operations and fragments, as opposed
+    /// to locations in the type system.
+    pub fn is_executable(&self) -> bool {
+        matches!(
+            self,
+            DirectiveLocation::Query
+                | DirectiveLocation::Mutation
+                | DirectiveLocation::Subscription
+                | DirectiveLocation::Field
+                | DirectiveLocation::FragmentDefinition
+                | DirectiveLocation::FragmentSpread
+                | DirectiveLocation::InlineFragment
+                | DirectiveLocation::VariableDefinition
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +443,10 @@ pub struct UnionDef {
     pub members: Vec<TypeRef>,
     pub directives: Option<Vec<Directive>>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,14 +454,29 @@ pub struct ScalarDef {
     pub name: String,
     pub directives: Option<Vec<Directive>>,
     pub description: Option<String>,
+    /// Set on the spec's built-in scalars from [`crate::built_ins`], which
+    /// printers omit.
+    #[serde(default)]
+    pub built_in: bool,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct TypeDef {
     pub name: String,
+    /// Interfaces named in `implements A & B`. Always empty for input types.
+    #[serde(default)]
+    pub interfaces: Vec<String>,
     pub fields: Vec<Field>,
     pub directives: Option<Vec<Directive>>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,22 +485,50 @@ pub struct Field {
     pub field_type: TypeRef,
     pub arguments: Option<Vec<InputValue>>,
     pub directives: Option<Vec<Directive>>,
+    /// From the node's `@deprecated` directive, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<Deprecation>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
+}
+
+/// Why a field, argument, input field or enum value should no longer be
+/// used, as declared by `@deprecated(reason: "...")`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Deprecation {
+    /// The given reason, or the spec's default "No longer supported".
+    pub reason: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Directive {
     pub name: String,
-    pub arguments: Option<Vec<InputValue>>,
+    pub arguments: Option<Vec<Argument>>,
+    #[serde(default)]
+    pub span: Span,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum TypeRef {
     Named(String),
     NonNull(Box<TypeRef>),
     List(Box<TypeRef>),
 }
 
+impl TypeRef {
+    /// The named type inside any list and non-null wrappers, e.g. `User` for
+    /// `[User!]!`.
+    pub fn named_type(&self) -> &str {
+        match self {
+            TypeRef::Named(name) => name,
+            TypeRef::NonNull(inner) | TypeRef::List(inner) => inner.named_type(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Value {
     String(String),
@@ -70,6 +539,21 @@ pub enum Value {
     Object(Vec<(String, Value)>),
     List(Vec<Value>),
     Null,
+    /// `$name`, only meaningful in executable documents.
+    Variable(String),
+}
+
+impl Value {
+    /// Returns `true` if the value references no variables, as default values
+    /// and the directives of type system definitions must.
+    pub fn is_const(&self) -> bool {
+        match self {
+            Value::Variable(_) => false,
+            Value::List(items) => items.iter().all(Value::is_const),
+            Value::Object(fields) => fields.iter().all(|(_, value)| value.is_const()),
+            _ => true,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -77,7 +561,29 @@ pub struct InputValue {
     pub name: String,
     pub value_type: TypeRef,
     pub default_value: Option<Value>,
+    #[serde(default)]
+    pub directives: Option<Vec<Directive>>,
+    /// From the node's `@deprecated` directive, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<Deprecation>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
+}
+
+/// `input Name @directives { fields }`
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct InputObjectDef {
+    pub name: String,
+    pub fields: Vec<InputValue>,
+    pub directives: Option<Vec<Directive>>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -86,11 +592,144 @@ pub struct EnumDef {
     pub values: Vec<EnumValue>,
     pub directives: Option<Vec<Directive>>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumValue {
     pub name: String,
     pub directives: Option<Vec<Directive>>,
+    /// From the node's `@deprecated` directive, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<Deprecation>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub span: Span,
+    #[serde(default, skip_serializing_if = "Comments::is_empty")]
+    pub comments: Comments,
+}
+
+/// A document of operations and fragments, as found in client `.graphql`
+/// files.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ExecutableDocument {
+    pub definitions: Vec<ExecutableDefinition>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ExecutableDefinition {
+    Operation(OperationDef),
+    Fragment(FragmentDef),
+}
+
+impl ExecutableDefinition {
+    pub fn span(&self) -> Span {
+        match self {
+            ExecutableDefinition::Operation(def) => def.span,
+            ExecutableDefinition::Fragment(def) => def.span,
+        }
+    }
+}
+
+/// `query Name($var: Type) @directives { ... }`. The `{ ... }` shorthand is a
+/// query without a name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OperationDef {
+    pub operation: OperationType,
+    pub name: Option<String>,
+    pub variables: Vec<VariableDef>,
+    pub directives: Option<Vec<Directive>>,
+    pub selection_set: Vec<Selection>,
+    #[serde(default)]
+    pub span: Span,
+}
+
+/// `$name: Type = default @directives`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VariableDef {
+    pub name: String,
+    pub var_type: TypeRef,
+    pub default_value: Option<Value>,
+    pub directives: Option<Vec<Directive>>,
+    #[serde(default)]
+    pub span: Span,
+}
+
+/// `fragment Name on Type @directives { ... }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FragmentDef {
+    pub name: String,
+    pub type_condition: String,
+    pub directives: Option<Vec<Directive>>,
+    pub selection_set: Vec<Selection>,
+    #[serde(default)]
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Selection {
+    Field(FieldSelection),
+    FragmentSpread(FragmentSpread),
+    InlineFragment(InlineFragment),
+}
+
+impl Selection {
+    pub fn span(&self) -> Span {
+        match self {
+            Selection::Field(field) => field.span,
+            Selection::FragmentSpread(spread) => spread.span,
+            Selection::InlineFragment(fragment) => fragment.span,
+        }
+    }
+}
+
+/// `alias: name(arguments) @directives { ... }`. The selection set is empty
+/// for leaf fields.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldSelection {
+    pub alias: Option<String>,
+    pub name: String,
+    pub arguments: Option<Vec<Argument>>,
+    pub directives: Option<Vec<Directive>>,
+    pub selection_set: Vec<Selection>,
+    #[serde(default)]
+    pub span: Span,
+}
+
+impl FieldSelection {
+    /// The key the field is returned under: its alias, or else its name.
+    pub fn response_key(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// `...FragmentName @directives`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FragmentSpread {
+    pub fragment_name: String,
+    pub directives: Option<Vec<Directive>>,
+    #[serde(default)]
+    pub span: Span,
+}
+
+/// `... on Type @directives { ... }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InlineFragment {
+    pub type_condition: Option<String>,
+    pub directives: Option<Vec<Directive>>,
+    pub selection_set: Vec<Selection>,
+    #[serde(default)]
+    pub span: Span,
+}
+
+/// `name: value` passed to a field or directive.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Argument {
+    pub name: String,
+    pub value: Value,
+    #[serde(default)]
+    pub span: Span,
 }
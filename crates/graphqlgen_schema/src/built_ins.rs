@@ -0,0 +1,219 @@
+use crate::ast::{
+    Comments, Definition, DirectiveDef, DirectiveLocation, InputValue, ScalarDef, Span, TypeRef,
+    Value,
+};
+
+/// The scalars every schema has without defining them.
+pub const BUILT_IN_SCALARS: &[&str] = &["Int", "Float", "String", "Boolean", "ID"];
+
+/// The directives every schema has without defining them.
+pub const BUILT_IN_DIRECTIVES: &[&str] = &["include", "skip", "deprecated", "specifiedBy", "oneOf"];
+
+/// The reason `@deprecated` gives when none is passed.
+pub const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// The built-in scalars and directives, with the definitions and
+/// descriptions of the spec, scalars first.
+pub fn built_in_definitions() -> Vec<Definition> {
+    let scalars = built_in_scalars().into_iter().map(Definition::Scalar);
+    let directives = built_in_directives().into_iter().map(Definition::Directive);
+    scalars.chain(directives).collect()
+}
+
+pub fn built_in_scalars() -> Vec<ScalarDef> {
+    vec![
+        scalar(
+            "Int",
+            "The `Int` scalar type represents non-fractional signed whole numeric values. \
+             Int can represent values between -(2^31) and 2^31 - 1.",
+        ),
+        scalar(
+            "Float",
+            "The `Float` scalar type represents signed double-precision fractional values as \
+             specified by [IEEE 754](https://en.wikipedia.org/wiki/IEEE_floating_point).",
+        ),
+        scalar(
+            "String",
+            "The `String` scalar type represents textual data, represented as UTF-8 character \
+             sequences. The String type is most often used by GraphQL to represent free-form \
+             human-readable text.",
+        ),
+        scalar(
+            "Boolean",
+            "The `Boolean` scalar type represents `true` or `false`.",
+        ),
+        scalar(
+            "ID",
+            "The `ID` scalar type represents a unique identifier, often used to refetch an \
+             object or as key for a cache. The ID type appears in a JSON response as a String; \
+             however, it is not intended to be human-readable. When expected as an input type, \
+             any string (such as `\"4\"`) or integer (such as `4`) input value will be accepted \
+             as an ID.",
+        ),
+    ]
+}
+
+pub fn built_in_directives() -> Vec<DirectiveDef> {
+    let conditional: Vec<DirectiveLocation> = vec![
+        DirectiveLocation::Field,
+        DirectiveLocation::FragmentSpread,
+        DirectiveLocation::InlineFragment,
+    ];
+
+    vec![
+        directive(
+            "include",
+            "Directs the executor to include this field or fragment only when the `if` argument \
+             is true.",
+            vec![argument(
+                "if",
+                non_null("Boolean"),
+                None,
+                "Included when true.",
+            )],
+            conditional.clone(),
+        ),
+        directive(
+            "skip",
+            "Directs the executor to skip this field or fragment when the `if` argument is true.",
+            vec![argument(
+                "if",
+                non_null("Boolean"),
+                None,
+                "Skipped when true.",
+            )],
+            conditional,
+        ),
+        directive(
+            "deprecated",
+            "Marks an element of a GraphQL schema as no longer supported.",
+            vec![argument(
+                "reason",
+                TypeRef::Named("String".to_string()),
+                Some(Value::String(DEFAULT_DEPRECATION_REASON.to_string())),
+                "Explains why this element was deprecated, usually also including a suggestion \
+                 for how to access supported similar data. Formatted using the Markdown syntax, \
+                 as specified by [CommonMark](https://commonmark.org/).",
+            )],
+            vec![
+                DirectiveLocation::FieldDefinition,
+                DirectiveLocation::ArgumentDefinition,
+                DirectiveLocation::InputFieldDefinition,
+                DirectiveLocation::EnumValue,
+            ],
+        ),
+        directive(
+            "specifiedBy",
+            "Exposes a URL that specifies the behavior of this scalar.",
+            vec![argument(
+                "url",
+                non_null("String"),
+                None,
+                "The URL that specifies the behavior of this scalar.",
+            )],
+            vec![DirectiveLocation::Scalar],
+        ),
+        directive(
+            "oneOf",
+            "Indicates exactly one field must be supplied and this field must not be `null`.",
+            Vec::new(),
+            vec![DirectiveLocation::InputObject],
+        ),
+    ]
+}
+
+fn scalar(name: &str, description: &str) -> ScalarDef {
+    ScalarDef {
+        name: name.to_string(),
+        directives: None,
+        description: Some(description.to_string()),
+        built_in: true,
+        span: Span::default(),
+        comments: Comments::default(),
+    }
+}
+
+fn directive(
+    name: &str,
+    description: &str,
+    arguments: Vec<InputValue>,
+    locations: Vec<DirectiveLocation>,
+) -> DirectiveDef {
+    DirectiveDef {
+        name: name.to_string(),
+        arguments: if arguments.is_empty() {
+            None
+        } else {
+            Some(arguments)
+        },
+        repeatable: false,
+        locations,
+        description: Some(description.to_string()),
+        built_in: true,
+        span: Span::default(),
+        comments: Comments::default(),
+    }
+}
+
+fn argument(
+    name: &str,
+    value_type: TypeRef,
+    default_value: Option<Value>,
+    description: &str,
+) -> InputValue {
+    InputValue {
+        name: name.to_string(),
+        value_type,
+        default_value,
+        directives: None,
+        deprecation: None,
+        description: Some(description.to_string()),
+        span: Span::default(),
+        comments: Comments::default(),
+    }
+}
+
+fn non_null(name: &str) -> TypeRef {
+    TypeRef::NonNull(Box::new(TypeRef::Named(name.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Document;
+    use crate::printer::{print_definition, print_document};
+
+    #[test]
+    fn test_built_in_names() {
+        let names: Vec<String> = built_in_definitions()
+            .iter()
+            .map(|def| match def {
+                Definition::Scalar(def) => def.name.clone(),
+                Definition::Directive(def) => format!("@{}", def.name),
+                _ => panic!("Expected a scalar or directive, got {:?}", def),
+            })
+            .collect();
+
+        let expected: Vec<String> = BUILT_IN_SCALARS
+            .iter()
+            .map(|name| name.to_string())
+            .chain(BUILT_IN_DIRECTIVES.iter().map(|name| format!("@{}", name)))
+            .collect();
+        assert_eq!(names, expected);
+        assert!(built_in_definitions().iter().all(Definition::is_built_in));
+    }
+
+    #[test]
+    fn test_print_built_ins() {
+        let deprecated: Definition = Definition::Directive(built_in_directives().remove(2));
+        assert!(print_definition(&deprecated).ends_with(
+            "  reason: String = \"No longer supported\"\n) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE"
+        ));
+
+        let document = Document {
+            definitions: built_in_definitions(),
+            comments: Vec::new(),
+        };
+        assert_eq!(print_document(&document), "");
+    }
+}
@@ -0,0 +1,263 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ast::Span;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Note => write!(f, "note"),
+        }
+    }
+}
+
+/// A source range with an (optionally empty) explanation shown under it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+}
+
+/// A problem found in a schema, pointing at the source it refers to.
+///
+/// `code` is a short stable identifier such as `unexpected-token` that tools
+/// can match on; `message` is the human readable summary.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: String,
+    pub message: String,
+    pub primary: Option<Label>,
+    pub secondary: Vec<Label>,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub fn new(severity: Severity, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            code: code.into(),
+            message: message.into(),
+            primary: None,
+            secondary: Vec::new(),
+            help: None,
+        }
+    }
+
+    pub fn error(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, code, message)
+    }
+
+    pub fn warning(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, code, message)
+    }
+
+    pub fn with_primary(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.primary = Some(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_secondary(mut self, span: Span, message: impl Into<String>) -> Self {
+        self.secondary.push(Label {
+            span,
+            message: message.into(),
+        });
+        self
+    }
+
+    pub fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+
+    /// The primary span, if the diagnostic has one.
+    pub fn span(&self) -> Option<Span> {
+        self.primary.as_ref().map(|label| label.span)
+    }
+
+    /// Renders the diagnostic as an annotated excerpt of `source`:
+    ///
+    /// ```text
+    /// error[unexpected-token]: Expected `:`, found name `ID`
+    ///  --> schema.graphql:2:6
+    ///   |
+    /// 2 |   id ID!
+    ///   |      ^^ expected `:`
+    /// ```
+    pub fn render(&self, source: &str, path: &str) -> String {
+        let lines: Vec<&str> = source.lines().collect();
+        let labels: Vec<(&Label, char)> = self
+            .primary
+            .iter()
+            .map(|label| (label, '^'))
+            .chain(self.secondary.iter().map(|label| (label, '-')))
+            .filter(|(label, _)| !label.span.is_dummy())
+            .collect();
+
+        let gutter: usize = labels
+            .iter()
+            .map(|(label, _)| label.span.start.line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let pad: String = " ".repeat(gutter);
+
+        let mut out: String = format!("{}[{}]: {}\n", self.severity, self.code, self.message);
+
+        match self.span().filter(|span| !span.is_dummy()) {
+            Some(span) => out.push_str(&format!(
+                "{}--> {}:{}:{}\n",
+                pad, path, span.start.line, span.start.column
+            )),
+            None => out.push_str(&format!("{}--> {}\n", pad, path)),
+        }
+
+        if !labels.is_empty() {
+            out.push_str(&format!("{} |\n", pad));
+        }
+
+        let mut ordered: Vec<&(&Label, char)> = labels.iter().collect();
+        ordered.sort_by_key(|(label, _)| label.span.start);
+
+        let mut previous_line: Option<usize> = None;
+        for (label, marker) in ordered {
+            let line_no: usize = label.span.start.line;
+            let line: &str = lines.get(line_no - 1).copied().unwrap_or("");
+
+            if let Some(previous) = previous_line {
+                if line_no > previous + 1 {
+                    out.push_str("...\n");
+                }
+            }
+            if previous_line != Some(line_no) {
+                out.push_str(&format!("{:>gutter$} | {}\n", line_no, line));
+            }
+            previous_line = Some(line_no);
+
+            // Reuse the source's own tabs so the markers line up with the text.
+            let indent: String = line
+                .chars()
+                .take(label.span.start.column.saturating_sub(1))
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+
+            let line_len: usize = line.chars().count();
+            let width: usize = if label.span.end.line == line_no {
+                label
+                    .span
+                    .end
+                    .column
+                    .saturating_sub(label.span.start.column)
+            } else {
+                (line_len + 1).saturating_sub(label.span.start.column)
+            };
+            let underline: String = marker.to_string().repeat(width.max(1));
+
+            let text: String = format!("{}{} {}", indent, underline, label.message);
+            out.push_str(&format!("{} | {}\n", pad, text.trim_end()));
+        }
+
+        if let Some(help) = &self.help {
+            if !labels.is_empty() {
+                out.push_str(&format!("{} |\n", pad));
+            }
+            out.push_str(&format!("{} = help: {}\n", pad, help));
+        }
+
+        out
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::Pos;
+
+    fn span(line: usize, start: usize, end: usize) -> Span {
+        Span::new(
+            Pos {
+                line,
+                column: start,
+                offset: 0,
+            },
+            Pos {
+                line,
+                column: end,
+                offset: 0,
+            },
+        )
+    }
+
+    #[test]
+    fn test_render_primary_label() {
+        let source = "type User {\n  id ID!\n}\n";
+        let diagnostic = Diagnostic::error("unexpected-token", "Expected Colon")
+            .with_primary(span(2, 6, 8), "expected `:` before the type")
+            .with_help("fields are declared as `name: Type`");
+
+        assert_eq!(
+            diagnostic.render(source, "schema.graphql"),
+            "error[unexpected-token]: Expected Colon\n \
+             --> schema.graphql:2:6\n  \
+             |\n\
+             2 |   id ID!\n  \
+             |      ^^ expected `:` before the type\n  \
+             |\n  \
+             = help: fields are declared as `name: Type`\n"
+        );
+    }
+
+    #[test]
+    fn test_render_secondary_label_on_other_line() {
+        let source = "type A {\n  a: [Int\n\n\n  b: Int\n}";
+        let diagnostic = Diagnostic::error("unexpected-token", "Expected BracketClose")
+            .with_primary(span(5, 3, 4), "expected `]`")
+            .with_secondary(span(2, 6, 7), "list opened here");
+
+        assert_eq!(
+            diagnostic.render(source, "s.graphql"),
+            "error[unexpected-token]: Expected BracketClose\n \
+             --> s.graphql:5:3\n  \
+             |\n\
+             2 |   a: [Int\n  \
+             |      - list opened here\n\
+             ...\n\
+             5 |   b: Int\n  \
+             |   ^ expected `]`\n"
+        );
+    }
+
+    #[test]
+    fn test_render_without_span() {
+        let diagnostic = Diagnostic::warning("empty-schema", "Schema has no definitions");
+
+        assert_eq!(
+            diagnostic.render("", "s.graphql"),
+            "warning[empty-schema]: Schema has no definitions\n--> s.graphql\n"
+        );
+    }
+}
@@ -1 +1,8 @@
 pub mod ast;
+pub mod built_ins;
+pub mod diagnostic;
+pub mod printer;
+pub mod schema;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod visit;
@@ -0,0 +1,599 @@
+//! Prints a [`Document`] back to GraphQL SDL.
+//!
+//! The output is canonical: definitions are separated by a blank line,
+//! fields, values and root operation types go one per line with two spaces
+//! of indentation, and descriptions are printed as block strings wherever a
+//! block string can hold the description unchanged. Comments attached to
+//! nodes are printed around them. Parsing the output yields the same
+//! document, apart from spans.
+
+use crate::ast::{
+    Comment, Comments, Definition, Directive, DirectiveDef, Document, EnumDef, EnumValue,
+    Extension, Field, InputObjectDef, InputValue, ScalarDef, SchemaDef, TypeDef, TypeRef, UnionDef,
+    Value,
+};
+
+const INDENT: &str = "  ";
+
+/// Prints every definition of `document` but the built-in ones, separated by
+/// blank lines.
+pub fn print_document(document: &Document) -> String {
+    let mut blocks: Vec<String> = document
+        .definitions
+        .iter()
+        .filter(|def| !def.is_built_in())
+        .map(print_definition)
+        .collect();
+    if !document.comments.is_empty() {
+        let mut comments = String::new();
+        print_comments(&mut comments, &document.comments, 0);
+        blocks.push(comments.trim_end().to_string());
+    }
+
+    let mut out: String = blocks.join("\n\n");
+
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out
+}
+
+pub fn print_definition(definition: &Definition) -> String {
+    let mut out = String::new();
+    let comments: &Comments = definition.comments();
+    print_leading_comments(&mut out, &comments.leading, 0);
+
+    match definition {
+        Definition::Type(def) => print_object(&mut out, "type", def),
+        Definition::Interface(def) => print_object(&mut out, "interface", def),
+        Definition::Input(def) => print_input_object(&mut out, def),
+        Definition::Scalar(def) => print_scalar(&mut out, def),
+        Definition::Union(def) => print_union(&mut out, def),
+        Definition::Enum(def) => print_enum(&mut out, def),
+        Definition::Schema(def) => print_schema(&mut out, def),
+        Definition::Directive(def) => print_directive_def(&mut out, def),
+        Definition::Extension(ext) => print_extension(&mut out, ext),
+    }
+    print_trailing_comment(&mut out, &comments.trailing);
+
+    // Definitions with a body print inner comments inside its braces.
+    let body: bool = matches!(
+        definition,
+        Definition::Type(_)
+            | Definition::Interface(_)
+            | Definition::Input(_)
+            | Definition::Enum(_)
+            | Definition::Schema(_)
+            | Definition::Extension(
+                Extension::Type(_)
+                    | Extension::Interface(_)
+                    | Extension::Input(_)
+                    | Extension::Enum(_)
+                    | Extension::Schema(_)
+            )
+    );
+    if !body {
+        for comment in &comments.inner {
+            out.push_str("\n#");
+            out.push_str(comment.text.trim_end());
+        }
+    }
+    out
+}
+
+/// Prints a type reference such as `[String!]!`.
+pub fn print_type_ref(type_ref: &TypeRef) -> String {
+    match type_ref {
+        TypeRef::Named(name) => name.clone(),
+        TypeRef::NonNull(inner) => format!("{}!", print_type_ref(inner)),
+        TypeRef::List(inner) => format!("[{}]", print_type_ref(inner)),
+    }
+}
+
+/// Prints an input value such as `{ limit: 10, tags: ["a"] }`.
+pub fn print_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => print_string(s),
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        // `Debug` keeps the `.0` of whole numbers, which would read back as
+        // an `Int` otherwise.
+        Value::Float(f) => format!("{:?}", f),
+        Value::Enum(name) => name.clone(),
+        Value::Null => "null".to_string(),
+        Value::Variable(name) => format!("${}", name),
+        Value::List(items) => format!(
+            "[{}]",
+            items.iter().map(print_value).collect::<Vec<_>>().join(", ")
+        ),
+        Value::Object(fields) if fields.is_empty() => "{}".to_string(),
+        Value::Object(fields) => format!(
+            "{{ {} }}",
+            fields
+                .iter()
+                .map(|(name, value)| format!("{}: {}", name, print_value(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Prints `value` as a `"quoted"` string, escaping what must be escaped.
+pub fn print_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// Returns `true` for lines a block string drops or dedents around.
+fn is_blank(line: &str) -> bool {
+    line.chars().all(|c| c == ' ' || c == '\t')
+}
+
+/// Returns `true` if `value` reads back unchanged from a block string. Block
+/// strings drop leading and trailing blank lines, remove the indentation
+/// shared by all lines, normalise line endings, and cannot hold control
+/// characters.
+fn fits_block_string(value: &str) -> bool {
+    let lines: Vec<&str> = value.split('\n').collect();
+    let first: &str = lines[0];
+    let last: &str = lines[lines.len() - 1];
+
+    let no_control: bool = !value
+        .chars()
+        .any(|c| c.is_control() && c != '\n' && c != '\t');
+    let unindented: bool = lines.len() == 1
+        || lines
+            .iter()
+            .any(|line| !is_blank(line) && !line.starts_with([' ', '\t']));
+
+    no_control && unindented && !is_blank(first) && !is_blank(last)
+}
+
+fn print_description(out: &mut String, description: &Option<String>, indent: usize) {
+    let Some(description) = description else {
+        return;
+    };
+    let pad: String = INDENT.repeat(indent);
+
+    if !fits_block_string(description) {
+        out.push_str(&format!("{}{}\n", pad, print_string(description)));
+        return;
+    }
+
+    let escaped: String = description.replace("\"\"\"", "\\\"\"\"");
+    if !escaped.contains('\n') && !escaped.ends_with(['"', '\\']) {
+        out.push_str(&format!("{}\"\"\"{}\"\"\"\n", pad, escaped));
+        return;
+    }
+
+    out.push_str(&format!("{}\"\"\"\n", pad));
+    for line in escaped.split('\n') {
+        if !line.is_empty() {
+            out.push_str(&pad);
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str(&format!("{}\"\"\"\n", pad));
+}
+
+/// Writes each comment on a line of its own, keeping a blank line between
+/// comments where the source had one.
+fn print_comments(out: &mut String, comments: &[Comment], indent: usize) {
+    for (i, comment) in comments.iter().enumerate() {
+        if i > 0 && comments[i - 1].blank_line_after {
+            out.push('\n');
+        }
+        out.push_str(&INDENT.repeat(indent));
+        out.push('#');
+        out.push_str(comment.text.trim_end());
+        out.push('\n');
+    }
+}
+
+/// Like [`print_comments`], also keeping a blank line between the comments
+/// and the node they lead.
+fn print_leading_comments(out: &mut String, comments: &[Comment], indent: usize) {
+    print_comments(out, comments, indent);
+    if comments.last().is_some_and(|c| c.blank_line_after) {
+        out.push('\n');
+    }
+}
+
+fn print_trailing_comment(out: &mut String, comment: &Option<Comment>) {
+    if let Some(comment) = comment {
+        out.push_str(" #");
+        out.push_str(comment.text.trim_end());
+    }
+}
+
+fn print_directives(out: &mut String, directives: &Option<Vec<Directive>>) {
+    for directive in directives.iter().flatten() {
+        out.push_str(" @");
+        out.push_str(&directive.name);
+
+        if let Some(arguments) = &directive.arguments {
+            let arguments: Vec<String> = arguments
+                .iter()
+                .map(|arg| format!("{}: {}", arg.name, print_value(&arg.value)))
+                .collect();
+            out.push_str(&format!("({})", arguments.join(", ")));
+        }
+    }
+}
+
+fn print_input_value(out: &mut String, input: &InputValue) {
+    out.push_str(&format!(
+        "{}: {}",
+        input.name,
+        print_type_ref(&input.value_type)
+    ));
+    if let Some(default) = &input.default_value {
+        out.push_str(" = ");
+        out.push_str(&print_value(default));
+    }
+    print_directives(out, &input.directives);
+}
+
+/// Prints an arguments definition on one line, or one argument per line if
+/// any of them has a description or comments.
+fn print_arguments_definition(
+    out: &mut String,
+    arguments: &Option<Vec<InputValue>>,
+    indent: usize,
+) {
+    let Some(arguments) = arguments else {
+        return;
+    };
+
+    if arguments
+        .iter()
+        .all(|arg| arg.description.is_none() && arg.comments.is_empty())
+    {
+        out.push('(');
+        for (i, arg) in arguments.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            print_input_value(out, arg);
+        }
+        out.push(')');
+        return;
+    }
+
+    out.push_str("(\n");
+    for arg in arguments {
+        print_leading_comments(out, &arg.comments.leading, indent + 1);
+        print_description(out, &arg.description, indent + 1);
+        out.push_str(&INDENT.repeat(indent + 1));
+        print_input_value(out, arg);
+        print_trailing_comment(out, &arg.comments.trailing);
+        out.push('\n');
+        print_comments(out, &arg.comments.inner, indent + 1);
+    }
+    out.push_str(&INDENT.repeat(indent));
+    out.push(')');
+}
+
+fn print_field(out: &mut String, field: &Field) {
+    print_leading_comments(out, &field.comments.leading, 1);
+    print_description(out, &field.description, 1);
+    out.push_str(INDENT);
+    out.push_str(&field.name);
+    print_arguments_definition(out, &field.arguments, 1);
+    out.push_str(": ");
+    out.push_str(&print_type_ref(&field.field_type));
+    print_directives(out, &field.directives);
+    print_trailing_comment(out, &field.comments.trailing);
+    out.push('\n');
+    print_comments(out, &field.comments.inner, 1);
+}
+
+/// Prints `{ ... }` around the lines `print_line` writes for each item and
+/// the `inner` comments, or nothing if there are neither.
+fn print_block<T>(
+    out: &mut String,
+    items: &[T],
+    inner: &[Comment],
+    print_line: impl Fn(&mut String, &T),
+) {
+    if items.is_empty() && inner.is_empty() {
+        return;
+    }
+    out.push_str(" {\n");
+    for item in items {
+        print_line(out, item);
+    }
+    print_comments(out, inner, 1);
+    out.push('}');
+}
+
+/// Prints a type, interface or input definition.
+fn print_object(out: &mut String, keyword: &str, def: &TypeDef) {
+    print_description(out, &def.description, 0);
+    out.push_str(&format!("{} {}", keyword, def.name));
+    if !def.interfaces.is_empty() {
+        out.push_str(" implements ");
+        out.push_str(&def.interfaces.join(" & "));
+    }
+    print_directives(out, &def.directives);
+    print_block(out, &def.fields, &def.comments.inner, print_field);
+}
+
+fn print_input_field(out: &mut String, field: &InputValue) {
+    print_leading_comments(out, &field.comments.leading, 1);
+    print_description(out, &field.description, 1);
+    out.push_str(INDENT);
+    print_input_value(out, field);
+    print_trailing_comment(out, &field.comments.trailing);
+    out.push('\n');
+    print_comments(out, &field.comments.inner, 1);
+}
+
+fn print_input_object(out: &mut String, def: &InputObjectDef) {
+    print_description(out, &def.description, 0);
+    out.push_str(&format!("input {}", def.name));
+    print_directives(out, &def.directives);
+    print_block(out, &def.fields, &def.comments.inner, print_input_field);
+}
+
+fn print_scalar(out: &mut String, def: &ScalarDef) {
+    print_description(out, &def.description, 0);
+    out.push_str(&format!("scalar {}", def.name));
+    print_directives(out, &def.directives);
+}
+
+fn print_union(out: &mut String, def: &UnionDef) {
+    print_description(out, &def.description, 0);
+    out.push_str(&format!("union {}", def.name));
+    print_directives(out, &def.directives);
+
+    if !def.members.is_empty() {
+        let members: Vec<String> = def.members.iter().map(print_type_ref).collect();
+        out.push_str(" = ");
+        out.push_str(&members.join(" | "));
+    }
+}
+
+fn print_enum_value(out: &mut String, value: &EnumValue) {
+    print_leading_comments(out, &value.comments.leading, 1);
+    print_description(out, &value.description, 1);
+    out.push_str(INDENT);
+    out.push_str(&value.name);
+    print_directives(out, &value.directives);
+    print_trailing_comment(out, &value.comments.trailing);
+    out.push('\n');
+    print_comments(out, &value.comments.inner, 1);
+}
+
+fn print_enum(out: &mut String, def: &EnumDef) {
+    print_description(out, &def.description, 0);
+    out.push_str(&format!("enum {}", def.name));
+    print_directives(out, &def.directives);
+    print_block(out, &def.values, &def.comments.inner, print_enum_value);
+}
+
+fn print_schema(out: &mut String, def: &SchemaDef) {
+    print_description(out, &def.description, 0);
+    out.push_str("schema");
+    print_directives(out, &def.directives);
+    print_block(
+        out,
+        &def.operation_types,
+        &def.comments.inner,
+        |out, root| {
+            print_leading_comments(out, &root.comments.leading, 1);
+            out.push_str(&format!(
+                "{}{}: {}",
+                INDENT,
+                root.operation.as_str(),
+                root.type_name
+            ));
+            print_trailing_comment(out, &root.comments.trailing);
+            out.push('\n');
+            print_comments(out, &root.comments.inner, 1);
+        },
+    );
+}
+
+fn print_directive_def(out: &mut String, def: &DirectiveDef) {
+    print_description(out, &def.description, 0);
+    out.push_str(&format!("directive @{}", def.name));
+    print_arguments_definition(out, &def.arguments, 0);
+    if def.repeatable {
+        out.push_str(" repeatable");
+    }
+
+    let locations: Vec<&str> = def.locations.iter().map(|l| l.as_str()).collect();
+    out.push_str(" on ");
+    out.push_str(&locations.join(" | "));
+}
+
+/// Prints `extend` followed by the definition. Extensions never have a
+/// description.
+fn print_extension(out: &mut String, ext: &Extension) {
+    out.push_str("extend ");
+    match ext {
+        Extension::Type(def) => print_object(out, "type", def),
+        Extension::Interface(def) => print_object(out, "interface", def),
+        Extension::Input(def) => print_input_object(out, def),
+        Extension::Scalar(def) => print_scalar(out, def),
+        Extension::Union(def) => print_union(out, def),
+        Extension::Enum(def) => print_enum(out, def),
+        Extension::Schema(def) => print_schema(out, def),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{DirectiveLocation, Span};
+
+    fn named(name: &str) -> TypeRef {
+        TypeRef::Named(name.to_string())
+    }
+
+    fn comment(text: &str) -> Comment {
+        Comment {
+            text: text.to_string(),
+            ..Comment::default()
+        }
+    }
+
+    fn description(desc: &str) -> String {
+        let mut out = String::new();
+        print_description(&mut out, &Some(desc.to_string()), 1);
+        out
+    }
+
+    #[test]
+    fn test_print_type_ref() {
+        let type_ref = TypeRef::NonNull(Box::new(TypeRef::List(Box::new(TypeRef::NonNull(
+            Box::new(named("String")),
+        )))));
+        assert_eq!(print_type_ref(&type_ref), "[String!]!");
+    }
+
+    #[test]
+    fn test_print_value() {
+        let value = Value::Object(vec![
+            ("limit".to_string(), Value::Int(-10)),
+            ("ratio".to_string(), Value::Float(1.0)),
+            (
+                "tags".to_string(),
+                Value::List(vec![Value::String("a\"b\n".to_string()), Value::Null]),
+            ),
+            ("order".to_string(), Value::Enum("DESC".to_string())),
+            ("empty".to_string(), Value::Object(Vec::new())),
+        ]);
+
+        assert_eq!(
+            print_value(&value),
+            r#"{ limit: -10, ratio: 1.0, tags: ["a\"b\n", null], order: DESC, empty: {} }"#
+        );
+    }
+
+    #[test]
+    fn test_print_string_escapes_control_characters() {
+        assert_eq!(print_string("tab\there\u{1}"), "\"tab\\there\\u0001\"");
+    }
+
+    #[test]
+    fn test_print_descriptions() {
+        assert_eq!(description("One line"), "  \"\"\"One line\"\"\"\n");
+        assert_eq!(
+            description("First\n\n  indented"),
+            "  \"\"\"\n  First\n\n    indented\n  \"\"\"\n"
+        );
+        assert_eq!(
+            description("Ends with \"quote\""),
+            "  \"\"\"\n  Ends with \"quote\"\n  \"\"\"\n"
+        );
+        assert_eq!(
+            description("Has \"\"\" inside"),
+            "  \"\"\"Has \\\"\"\" inside\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn test_descriptions_a_block_string_cannot_hold_are_quoted() {
+        assert_eq!(description("\nleading blank"), "  \"\\nleading blank\"\n");
+        assert_eq!(
+            description("  all\n  indented"),
+            "  \"  all\\n  indented\"\n"
+        );
+        assert_eq!(description("cr\r\nlf"), "  \"cr\\r\\nlf\"\n");
+    }
+
+    #[test]
+    fn test_print_document() {
+        let document = Document {
+            definitions: vec![
+                Definition::Type(TypeDef {
+                    name: "Query".to_string(),
+                    fields: vec![Field {
+                        name: "posts".to_string(),
+                        field_type: TypeRef::List(Box::new(named("Post"))),
+                        arguments: Some(vec![InputValue {
+                            name: "first".to_string(),
+                            value_type: named("Int"),
+                            default_value: Some(Value::Int(10)),
+                            directives: None,
+                            deprecation: None,
+                            description: None,
+                            span: Span::default(),
+                            comments: Comments::default(),
+                        }]),
+                        directives: None,
+                        deprecation: None,
+                        description: Some("Newest first".to_string()),
+                        span: Span::default(),
+                        comments: Comments {
+                            trailing: Some(comment(" paginated")),
+                            ..Comments::default()
+                        },
+                    }],
+                    comments: Comments {
+                        leading: vec![
+                            Comment {
+                                blank_line_after: true,
+                                ..comment(" License")
+                            },
+                            comment(" Entry point"),
+                        ],
+                        inner: vec![comment(" more soon")],
+                        ..Comments::default()
+                    },
+                    ..TypeDef::default()
+                }),
+                Definition::Directive(DirectiveDef {
+                    name: "tag".to_string(),
+                    arguments: None,
+                    repeatable: true,
+                    locations: vec![DirectiveLocation::Object, DirectiveLocation::Union],
+                    description: None,
+                    built_in: false,
+                    span: Span::default(),
+                    comments: Comments::default(),
+                }),
+                Definition::Extension(Extension::Union(UnionDef {
+                    name: "Media".to_string(),
+                    members: Vec::new(),
+                    directives: Some(vec![Directive {
+                        name: "tag".to_string(),
+                        arguments: None,
+                        span: Span::default(),
+                    }]),
+                    description: None,
+                    span: Span::default(),
+                    comments: Comments::default(),
+                })),
+            ],
+            comments: vec![comment(" end")],
+        };
+
+        assert_eq!(
+            print_document(&document),
+            "# License\n\n# Entry point\n\
+             type Query {\n  \"\"\"Newest first\"\"\"\n  posts(first: Int = 10): [Post] # paginated\n  # more soon\n}\n\n\
+             directive @tag repeatable on OBJECT | UNION\n\n\
+             extend union Media @tag\n\n\
+             # end\n"
+        );
+    }
+}
@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+
+use crate::ast::{
+    Definition, DirectiveDef, Document, EnumDef, Field, InputObjectDef, InputValue, OperationType,
+    ScalarDef, TypeDef, TypeRef, UnionDef,
+};
+use crate::built_ins::{built_in_directives, built_in_scalars};
+
+/// A named type of a [`Schema`].
+#[derive(Debug, Clone)]
+pub enum NamedType {
+    Scalar(ScalarDef),
+    Object(TypeDef),
+    Interface(TypeDef),
+    Union(UnionDef),
+    Enum(EnumDef),
+    InputObject(InputObjectDef),
+}
+
+impl NamedType {
+    pub fn name(&self) -> &str {
+        match self {
+            NamedType::Scalar(def) => &def.name,
+            NamedType::Object(def) | NamedType::Interface(def) => &def.name,
+            NamedType::InputObject(def) => &def.name,
+            NamedType::Union(def) => &def.name,
+            NamedType::Enum(def) => &def.name,
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            NamedType::Scalar(def) => def.description.as_deref(),
+            NamedType::Object(def) | NamedType::Interface(def) => def.description.as_deref(),
+            NamedType::InputObject(def) => def.description.as_deref(),
+            NamedType::Union(def) => def.description.as_deref(),
+            NamedType::Enum(def) => def.description.as_deref(),
+        }
+    }
+
+    /// The fields of an object or interface type, empty for any other type.
+    pub fn fields(&self) -> &[Field] {
+        match self {
+            NamedType::Object(def) | NamedType::Interface(def) => &def.fields,
+            _ => &[],
+        }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields().iter().find(|field| field.name == name)
+    }
+
+    /// The fields of an input object type, empty for any other type.
+    pub fn input_fields(&self) -> &[InputValue] {
+        match self {
+            NamedType::InputObject(def) => &def.fields,
+            _ => &[],
+        }
+    }
+
+    pub fn input_field(&self, name: &str) -> Option<&InputValue> {
+        self.input_fields().iter().find(|field| field.name == name)
+    }
+
+    /// Returns `true` for scalars, enums and input objects, the types
+    /// arguments and input fields can have.
+    pub fn is_input_type(&self) -> bool {
+        matches!(
+            self,
+            NamedType::Scalar(_) | NamedType::Enum(_) | NamedType::InputObject(_)
+        )
+    }
+
+    /// Returns `true` for every type but input objects, the types fields can
+    /// have.
+    pub fn is_output_type(&self) -> bool {
+        !matches!(self, NamedType::InputObject(_))
+    }
+
+    /// Returns `true` for interfaces and unions, whose values are one of
+    /// their [possible types](Schema::possible_types).
+    pub fn is_abstract(&self) -> bool {
+        matches!(self, NamedType::Interface(_) | NamedType::Union(_))
+    }
+}
+
+/// A schema resolved from a [`Document`], for looking types up by name
+/// instead of walking its definitions.
+///
+/// Expects a document whose extensions have been merged, as the host sends
+/// to plugins; any extensions left are ignored, as are later definitions of
+/// a name that is already taken. The built-in scalars and directives from
+/// [`crate::built_ins`] are part of every schema, unless the document defines
+/// them itself.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    description: Option<String>,
+    /// Named types in definition order, after the built-in scalars.
+    types: Vec<NamedType>,
+    type_indices: HashMap<String, usize>,
+    directives: Vec<DirectiveDef>,
+    directive_indices: HashMap<String, usize>,
+    root_operation_types: Vec<(OperationType, String)>,
+    implementers: HashMap<String, Vec<String>>,
+    possible_types: HashMap<String, Vec<String>>,
+}
+
+impl Schema {
+    pub fn new(document: &Document) -> Self {
+        let mut schema: Schema = Schema::default();
+
+        // The document's own definitions of built-ins take precedence.
+        for scalar in built_in_scalars() {
+            if document
+                .definitions
+                .iter()
+                .all(|def| def.name() != Some(&scalar.name))
+            {
+                schema.insert_type(NamedType::Scalar(scalar));
+            }
+        }
+        for directive in built_in_directives() {
+            let defined: bool = document
+                .definitions
+                .iter()
+                .any(|def| matches!(def, Definition::Directive(own) if own.name == directive.name));
+            if !defined {
+                schema.insert_directive(directive);
+            }
+        }
+
+        for definition in &document.definitions {
+            let named_type: NamedType = match definition {
+                Definition::Type(def) => NamedType::Object(def.clone()),
+                Definition::Scalar(def) => NamedType::Scalar(def.clone()),
+                Definition::Input(def) => NamedType::InputObject(def.clone()),
+                Definition::Interface(def) => NamedType::Interface(def.clone()),
+                Definition::Union(def) => NamedType::Union(def.clone()),
+                Definition::Enum(def) => NamedType::Enum(def.clone()),
+                Definition::Schema(def) => {
+                    schema.description = schema.description.or(def.description.clone());
+                    continue;
+                }
+                Definition::Directive(def) => {
+                    schema.insert_directive(def.clone());
+                    continue;
+                }
+                Definition::Extension(_) => continue,
+            };
+            schema.insert_type(named_type);
+        }
+
+        for (operation, name) in document.root_operation_types() {
+            schema
+                .root_operation_types
+                .push((operation, name.to_string()));
+        }
+        schema.collect_possible_types();
+        schema
+    }
+
+    fn insert_type(&mut self, named_type: NamedType) {
+        if !self.type_indices.contains_key(named_type.name()) {
+            self.type_indices
+                .insert(named_type.name().to_string(), self.types.len());
+            self.types.push(named_type);
+        }
+    }
+
+    fn insert_directive(&mut self, directive: DirectiveDef) {
+        if !self.directive_indices.contains_key(&directive.name) {
+            self.directive_indices
+                .insert(directive.name.clone(), self.directives.len());
+            self.directives.push(directive);
+        }
+    }
+
+    fn collect_possible_types(&mut self) {
+        for named_type in &self.types {
+            match named_type {
+                NamedType::Object(def) | NamedType::Interface(def) => {
+                    for interface in &def.interfaces {
+                        self.implementers
+                            .entry(interface.clone())
+                            .or_default()
+                            .push(def.name.clone());
+                        if let NamedType::Object(_) = named_type {
+                            self.possible_types
+                                .entry(interface.clone())
+                                .or_default()
+                                .push(def.name.clone());
+                        }
+                    }
+                    if let NamedType::Object(_) = named_type {
+                        self.possible_types
+                            .insert(def.name.clone(), vec![def.name.clone()]);
+                    }
+                }
+                NamedType::Union(def) => {
+                    let members: Vec<String> = def
+                        .members
+                        .iter()
+                        .map(|member| member.named_type().to_string())
+                        .collect();
+                    self.possible_types.insert(def.name.clone(), members);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// The description of the `schema` definition.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Every named type, in definition order after the built-in scalars.
+    pub fn types(&self) -> &[NamedType] {
+        &self.types
+    }
+
+    pub fn get_type(&self, name: &str) -> Option<&NamedType> {
+        self.type_indices.get(name).map(|&index| &self.types[index])
+    }
+
+    /// The object type called `name`, `None` if there is none or it is
+    /// another kind of type.
+    pub fn object(&self, name: &str) -> Option<&TypeDef> {
+        match self.get_type(name) {
+            Some(NamedType::Object(def)) => Some(def),
+            _ => None,
+        }
+    }
+
+    /// The named type inside `type_ref`, e.g. the `User` type for `[User!]!`.
+    pub fn resolve(&self, type_ref: &TypeRef) -> Option<&NamedType> {
+        self.get_type(type_ref.named_type())
+    }
+
+    /// The field `field_name` of the object or interface type `type_name`.
+    pub fn field(&self, type_name: &str, field_name: &str) -> Option<&Field> {
+        self.get_type(type_name)?.field(field_name)
+    }
+
+    /// The named type of the field `field_name` of `type_name`, e.g. the
+    /// `User` type for `Query.user`.
+    pub fn field_type(&self, type_name: &str, field_name: &str) -> Option<&NamedType> {
+        self.resolve(&self.field(type_name, field_name)?.field_type)
+    }
+
+    /// Every directive, in definition order after the built-in directives.
+    pub fn directives(&self) -> &[DirectiveDef] {
+        &self.directives
+    }
+
+    pub fn directive(&self, name: &str) -> Option<&DirectiveDef> {
+        self.directive_indices
+            .get(name)
+            .map(|&index| &self.directives[index])
+    }
+
+    /// The object type used for `operation`, see
+    /// [`Document::root_operation_type`].
+    pub fn root_operation_type(&self, operation: OperationType) -> Option<&TypeDef> {
+        self.root_operation_types
+            .iter()
+            .find(|(root, _)| *root == operation)
+            .and_then(|(_, name)| self.object(name))
+    }
+
+    /// The root operation types of the schema, in `query`, `mutation`,
+    /// `subscription` order.
+    pub fn root_operation_types(&self) -> Vec<(OperationType, &TypeDef)> {
+        OperationType::ALL
+            .iter()
+            .filter_map(|&operation| {
+                self.root_operation_type(operation)
+                    .map(|def| (operation, def))
+            })
+            .collect()
+    }
+
+    /// The objects and interfaces that declare they implement `interface`,
+    /// in definition order.
+    pub fn implementers(&self, interface: &str) -> &[String] {
+        self.implementers
+            .get(interface)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// The object types a value of type `name` can have: the members of a
+    /// union, the objects implementing an interface, or an object type
+    /// itself.
+    pub fn possible_types(&self, name: &str) -> &[String] {
+        self.possible_types
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Returns `true` if a value of type `name` can be of the object type
+    /// `object`.
+    pub fn is_possible_type(&self, name: &str, object: &str) -> bool {
+        self.possible_types(name)
+            .iter()
+            .any(|possible| possible == object)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Comments, RootOperationTypeDef, SchemaDef, Span};
+    use crate::built_ins::BUILT_IN_DIRECTIVES;
+    use crate::test_utils::{document, union_def};
+
+    #[test]
+    fn test_looks_up_types_and_fields() {
+        let schema: Schema = Schema::new(&document());
+
+        assert!(matches!(
+            schema.get_type("User"),
+            Some(NamedType::Object(_))
+        ));
+        assert!(matches!(schema.get_type("ID"), Some(NamedType::Scalar(_))));
+        assert!(schema.get_type("Missing").is_none());
+        assert!(schema.object("Node").is_none());
+
+        let users: &NamedType = schema.field_type("Query", "users").unwrap();
+        assert_eq!(users.name(), "User");
+        assert!(schema.field("Filter", "id").is_none());
+        let filter: &NamedType = schema.get_type("Filter").unwrap();
+        assert_eq!(filter.input_field("id").unwrap().name, "id");
+        assert!(schema.field_type("Query", "missing").is_none());
+        assert!(schema.get_type("Filter").unwrap().is_input_type());
+        assert!(!schema.get_type("Filter").unwrap().is_output_type());
+
+        let names: Vec<&str> = schema.types().iter().map(NamedType::name).collect();
+        assert_eq!(
+            &names[..6],
+            &["Int", "Float", "String", "Boolean", "ID", "Node"]
+        );
+    }
+
+    #[test]
+    fn test_possible_types() {
+        let schema: Schema = Schema::new(&document());
+
+        assert_eq!(schema.implementers("Node"), &["Entity", "User", "Post"]);
+        assert_eq!(schema.possible_types("Node"), &["User", "Post"]);
+        assert_eq!(schema.possible_types("Entity"), &["User"]);
+        assert_eq!(schema.possible_types("SearchResult"), &["User", "Post"]);
+        assert_eq!(schema.possible_types("User"), &["User"]);
+        assert!(schema.possible_types("ID").is_empty());
+        assert!(schema.is_possible_type("SearchResult", "Post"));
+        assert!(!schema.is_possible_type("Entity", "Post"));
+    }
+
+    #[test]
+    fn test_root_operation_types() {
+        let mut document: Document = document();
+        let schema: Schema = Schema::new(&document);
+
+        assert_eq!(
+            schema
+                .root_operation_type(OperationType::Query)
+                .unwrap()
+                .name,
+            "Query"
+        );
+        assert!(schema
+            .root_operation_type(OperationType::Mutation)
+            .is_none());
+
+        document.definitions.push(Definition::Schema(SchemaDef {
+            operation_types: vec![RootOperationTypeDef {
+                operation: OperationType::Mutation,
+                type_name: "User".to_string(),
+                span: Span::default(),
+                comments: Comments::default(),
+            }],
+            directives: None,
+            description: Some("The API".to_string()),
+            span: Span::default(),
+            comments: Comments::default(),
+        }));
+        let schema: Schema = Schema::new(&document);
+
+        let roots: Vec<(OperationType, &str)> = schema
+            .root_operation_types()
+            .into_iter()
+            .map(|(operation, def)| (operation, def.name.as_str()))
+            .collect();
+        assert_eq!(roots, vec![(OperationType::Mutation, "User")]);
+        assert_eq!(schema.description(), Some("The API"));
+    }
+
+    #[test]
+    fn test_built_ins() {
+        let mut document: Document = document();
+        let schema: Schema = Schema::new(&document);
+
+        assert!(matches!(
+            schema.get_type("Int"),
+            Some(NamedType::Scalar(ScalarDef { built_in: true, .. }))
+        ));
+        let names: Vec<&str> = schema
+            .directives()
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(names, BUILT_IN_DIRECTIVES);
+
+        let mut deprecated: DirectiveDef = built_in_directives().remove(2);
+        deprecated.built_in = false;
+        deprecated.description = None;
+        document.definitions.push(Definition::Directive(deprecated));
+        let schema: Schema = Schema::new(&document);
+
+        assert!(!schema.directive("deprecated").unwrap().built_in);
+        assert_eq!(schema.directives().len(), BUILT_IN_DIRECTIVES.len());
+    }
+
+    #[test]
+    fn test_first_definition_of_a_name_wins() {
+        let mut document: Document = document();
+        document
+            .definitions
+            .push(Definition::Union(union_def("User", &["Post"])));
+        document.definitions.push(Definition::Scalar(ScalarDef {
+            name: "ID".to_string(),
+            directives: None,
+            description: Some("Custom".to_string()),
+            built_in: false,
+            span: Span::default(),
+            comments: Comments::default(),
+        }));
+        let schema: Schema = Schema::new(&document);
+
+        assert!(schema.object("User").is_some());
+        assert_eq!(schema.get_type("ID").unwrap().description(), Some("Custom"));
+        assert_eq!(
+            schema.types().iter().filter(|t| t.name() == "ID").count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,99 @@
+//! Builders for the AST nodes that tests start from. Other crates of the
+//! workspace get them through the `test-utils` feature.
+
+use crate::ast::{
+    Comments, Definition, Document, Field, InputObjectDef, InputValue, Span, TypeDef, TypeRef,
+    UnionDef, Value,
+};
+
+pub fn named(name: &str) -> TypeRef {
+    TypeRef::Named(name.to_string())
+}
+
+pub fn field(name: &str, field_type: TypeRef) -> Field {
+    Field {
+        name: name.to_string(),
+        field_type,
+        arguments: None,
+        directives: None,
+        deprecation: None,
+        description: None,
+        span: Span::default(),
+        comments: Comments::default(),
+    }
+}
+
+pub fn type_def(name: &str, interfaces: &[&str], fields: Vec<Field>) -> TypeDef {
+    TypeDef {
+        name: name.to_string(),
+        interfaces: interfaces.iter().map(|i| i.to_string()).collect(),
+        fields,
+        directives: None,
+        description: None,
+        span: Span::default(),
+        comments: Comments::default(),
+    }
+}
+
+pub fn input_value(name: &str, value_type: TypeRef, default_value: Option<Value>) -> InputValue {
+    InputValue {
+        name: name.to_string(),
+        value_type,
+        default_value,
+        directives: None,
+        deprecation: None,
+        description: None,
+        span: Span::default(),
+        comments: Comments::default(),
+    }
+}
+
+pub fn union_def(name: &str, members: &[&str]) -> UnionDef {
+    UnionDef {
+        name: name.to_string(),
+        members: members.iter().map(|m| named(m)).collect(),
+        directives: None,
+        description: None,
+        span: Span::default(),
+        comments: Comments::default(),
+    }
+}
+
+/// A schema with interfaces implementing interfaces, a union, an input type
+/// and a `Query` root, without a schema definition.
+pub fn document() -> Document {
+    Document {
+        definitions: vec![
+            Definition::Interface(type_def("Node", &[], vec![field("id", named("ID"))])),
+            Definition::Interface(type_def(
+                "Entity",
+                &["Node"],
+                vec![field("id", named("ID"))],
+            )),
+            Definition::Type(type_def(
+                "User",
+                &["Entity", "Node"],
+                vec![field("id", named("ID"))],
+            )),
+            Definition::Type(type_def("Post", &["Node"], vec![field("id", named("ID"))])),
+            Definition::Union(union_def("SearchResult", &["User", "Post"])),
+            Definition::Type(type_def(
+                "Query",
+                &[],
+                vec![
+                    field(
+                        "users",
+                        TypeRef::NonNull(Box::new(TypeRef::List(Box::new(named("User"))))),
+                    ),
+                    field("node", named("Node")),
+                ],
+            )),
+            Definition::Input(InputObjectDef {
+                name: "Filter".to_string(),
+                fields: vec![input_value("id", named("ID"), None)],
+                ..InputObjectDef::default()
+            }),
+        ],
+        comments: Vec::new(),
+    }
+}
@@ -0,0 +1,1083 @@
+//! Traversal of schema and executable documents.
+//!
+//! [`Visitor`] observes a document through `enter_*` and `leave_*` hooks
+//! called around each node, driven by [`walk_document`],
+//! [`walk_executable_document`] and the other `walk_*` functions.
+//! [`VisitMut`] rewrites a document in place: each `visit_*_mut` method walks
+//! the node's children by default, so an implementation only overrides the
+//! node kinds it changes and calls the matching `walk_*_mut` function to keep
+//! descending.
+
+use crate::ast::{
+    Argument, Definition, Directive, DirectiveDef, Document, EnumDef, EnumValue,
+    ExecutableDefinition, ExecutableDocument, Extension, Field, FieldSelection, FragmentDef,
+    FragmentSpread, InlineFragment, InputObjectDef, InputValue, OperationDef, RootOperationTypeDef,
+    ScalarDef, SchemaDef, Selection, TypeDef, TypeRef, UnionDef, Value, VariableDef,
+};
+
+/// Read-only hooks called while walking a document. Every hook does nothing
+/// by default.
+///
+/// Object and interface types share [`TypeDef`] and get their own hooks.
+/// Arguments and input fields both go through the `input_value` hooks, and
+/// the `type_ref` and `value` hooks are called again for every wrapped type
+/// and nested value.
+#[allow(unused_variables)]
+pub trait Visitor<'ast> {
+    fn enter_document(&mut self, document: &'ast Document) {}
+    fn leave_document(&mut self, document: &'ast Document) {}
+
+    fn enter_definition(&mut self, definition: &'ast Definition) {}
+    fn leave_definition(&mut self, definition: &'ast Definition) {}
+
+    fn enter_extension(&mut self, extension: &'ast Extension) {}
+    fn leave_extension(&mut self, extension: &'ast Extension) {}
+
+    fn enter_object(&mut self, object: &'ast TypeDef) {}
+    fn leave_object(&mut self, object: &'ast TypeDef) {}
+
+    fn enter_interface(&mut self, interface: &'ast TypeDef) {}
+    fn leave_interface(&mut self, interface: &'ast TypeDef) {}
+
+    fn enter_scalar(&mut self, scalar: &'ast ScalarDef) {}
+    fn leave_scalar(&mut self, scalar: &'ast ScalarDef) {}
+
+    fn enter_input_object(&mut self, input: &'ast InputObjectDef) {}
+    fn leave_input_object(&mut self, input: &'ast InputObjectDef) {}
+
+    fn enter_union(&mut self, union: &'ast UnionDef) {}
+    fn leave_union(&mut self, union: &'ast UnionDef) {}
+
+    fn enter_enum(&mut self, enum_def: &'ast EnumDef) {}
+    fn leave_enum(&mut self, enum_def: &'ast EnumDef) {}
+
+    fn enter_enum_value(&mut self, value: &'ast EnumValue) {}
+    fn leave_enum_value(&mut self, value: &'ast EnumValue) {}
+
+    fn enter_schema(&mut self, schema: &'ast SchemaDef) {}
+    fn leave_schema(&mut self, schema: &'ast SchemaDef) {}
+
+    fn enter_root_operation_type(&mut self, root: &'ast RootOperationTypeDef) {}
+    fn leave_root_operation_type(&mut self, root: &'ast RootOperationTypeDef) {}
+
+    fn enter_directive_def(&mut self, directive: &'ast DirectiveDef) {}
+    fn leave_directive_def(&mut self, directive: &'ast DirectiveDef) {}
+
+    fn enter_field(&mut self, field: &'ast Field) {}
+    fn leave_field(&mut self, field: &'ast Field) {}
+
+    fn enter_input_value(&mut self, input_value: &'ast InputValue) {}
+    fn leave_input_value(&mut self, input_value: &'ast InputValue) {}
+
+    fn enter_executable_document(&mut self, document: &'ast ExecutableDocument) {}
+    fn leave_executable_document(&mut self, document: &'ast ExecutableDocument) {}
+
+    fn enter_executable_definition(&mut self, definition: &'ast ExecutableDefinition) {}
+    fn leave_executable_definition(&mut self, definition: &'ast ExecutableDefinition) {}
+
+    fn enter_operation(&mut self, operation: &'ast OperationDef) {}
+    fn leave_operation(&mut self, operation: &'ast OperationDef) {}
+
+    fn enter_variable_def(&mut self, variable: &'ast VariableDef) {}
+    fn leave_variable_def(&mut self, variable: &'ast VariableDef) {}
+
+    fn enter_fragment(&mut self, fragment: &'ast FragmentDef) {}
+    fn leave_fragment(&mut self, fragment: &'ast FragmentDef) {}
+
+    fn enter_selection(&mut self, selection: &'ast Selection) {}
+    fn leave_selection(&mut self, selection: &'ast Selection) {}
+
+    fn enter_field_selection(&mut self, field: &'ast FieldSelection) {}
+    fn leave_field_selection(&mut self, field: &'ast FieldSelection) {}
+
+    fn enter_fragment_spread(&mut self, spread: &'ast FragmentSpread) {}
+    fn leave_fragment_spread(&mut self, spread: &'ast FragmentSpread) {}
+
+    fn enter_inline_fragment(&mut self, fragment: &'ast InlineFragment) {}
+    fn leave_inline_fragment(&mut self, fragment: &'ast InlineFragment) {}
+
+    fn enter_directive(&mut self, directive: &'ast Directive) {}
+    fn leave_directive(&mut self, directive: &'ast Directive) {}
+
+    fn enter_argument(&mut self, argument: &'ast Argument) {}
+    fn leave_argument(&mut self, argument: &'ast Argument) {}
+
+    fn enter_type_ref(&mut self, type_ref: &'ast TypeRef) {}
+    fn leave_type_ref(&mut self, type_ref: &'ast TypeRef) {}
+
+    /// Called for every reference to a type by name: the innermost type of a
+    /// [`TypeRef`], implemented interfaces, root operation types and type
+    /// conditions. Not called for the names of definitions themselves.
+    fn visit_named_type(&mut self, name: &'ast str) {}
+
+    fn enter_value(&mut self, value: &'ast Value) {}
+    fn leave_value(&mut self, value: &'ast Value) {}
+}
+
+pub fn walk_document<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, document: &'ast Document) {
+    visitor.enter_document(document);
+    for definition in &document.definitions {
+        walk_definition(visitor, definition);
+    }
+    visitor.leave_document(document);
+}
+
+pub fn walk_definition<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    definition: &'ast Definition,
+) {
+    visitor.enter_definition(definition);
+    match definition {
+        Definition::Type(def) => walk_object(visitor, def),
+        Definition::Interface(def) => walk_interface(visitor, def),
+        Definition::Scalar(def) => walk_scalar(visitor, def),
+        Definition::Input(def) => walk_input_object(visitor, def),
+        Definition::Union(def) => walk_union(visitor, def),
+        Definition::Enum(def) => walk_enum(visitor, def),
+        Definition::Schema(def) => walk_schema(visitor, def),
+        Definition::Directive(def) => walk_directive_def(visitor, def),
+        Definition::Extension(extension) => walk_extension(visitor, extension),
+    }
+    visitor.leave_definition(definition);
+}
+
+/// Walks an extension through the same hooks as the definition it extends.
+pub fn walk_extension<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    extension: &'ast Extension,
+) {
+    visitor.enter_extension(extension);
+    match extension {
+        Extension::Type(def) => walk_object(visitor, def),
+        Extension::Interface(def) => walk_interface(visitor, def),
+        Extension::Scalar(def) => walk_scalar(visitor, def),
+        Extension::Input(def) => walk_input_object(visitor, def),
+        Extension::Union(def) => walk_union(visitor, def),
+        Extension::Enum(def) => walk_enum(visitor, def),
+        Extension::Schema(def) => walk_schema(visitor, def),
+    }
+    visitor.leave_extension(extension);
+}
+
+pub fn walk_object<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, object: &'ast TypeDef) {
+    visitor.enter_object(object);
+    walk_type_def_children(visitor, object);
+    visitor.leave_object(object);
+}
+
+pub fn walk_interface<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, interface: &'ast TypeDef) {
+    visitor.enter_interface(interface);
+    walk_type_def_children(visitor, interface);
+    visitor.leave_interface(interface);
+}
+
+fn walk_type_def_children<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, def: &'ast TypeDef) {
+    for interface in &def.interfaces {
+        visitor.visit_named_type(interface);
+    }
+    walk_directives(visitor, &def.directives);
+    for field in &def.fields {
+        walk_field(visitor, field);
+    }
+}
+
+pub fn walk_scalar<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, scalar: &'ast ScalarDef) {
+    visitor.enter_scalar(scalar);
+    walk_directives(visitor, &scalar.directives);
+    visitor.leave_scalar(scalar);
+}
+
+pub fn walk_input_object<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    input: &'ast InputObjectDef,
+) {
+    visitor.enter_input_object(input);
+    walk_directives(visitor, &input.directives);
+    for field in &input.fields {
+        walk_input_value(visitor, field);
+    }
+    visitor.leave_input_object(input);
+}
+
+pub fn walk_union<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, union: &'ast UnionDef) {
+    visitor.enter_union(union);
+    walk_directives(visitor, &union.directives);
+    for member in &union.members {
+        walk_type_ref(visitor, member);
+    }
+    visitor.leave_union(union);
+}
+
+pub fn walk_enum<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, enum_def: &'ast EnumDef) {
+    visitor.enter_enum(enum_def);
+    walk_directives(visitor, &enum_def.directives);
+    for value in &enum_def.values {
+        walk_enum_value(visitor, value);
+    }
+    visitor.leave_enum(enum_def);
+}
+
+pub fn walk_enum_value<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, value: &'ast EnumValue) {
+    visitor.enter_enum_value(value);
+    walk_directives(visitor, &value.directives);
+    visitor.leave_enum_value(value);
+}
+
+pub fn walk_schema<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, schema: &'ast SchemaDef) {
+    visitor.enter_schema(schema);
+    walk_directives(visitor, &schema.directives);
+    for root in &schema.operation_types {
+        walk_root_operation_type(visitor, root);
+    }
+    visitor.leave_schema(schema);
+}
+
+pub fn walk_root_operation_type<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    root: &'ast RootOperationTypeDef,
+) {
+    visitor.enter_root_operation_type(root);
+    visitor.visit_named_type(&root.type_name);
+    visitor.leave_root_operation_type(root);
+}
+
+pub fn walk_directive_def<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    directive: &'ast DirectiveDef,
+) {
+    visitor.enter_directive_def(directive);
+    for argument in directive.arguments.iter().flatten() {
+        walk_input_value(visitor, argument);
+    }
+    visitor.leave_directive_def(directive);
+}
+
+pub fn walk_field<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, field: &'ast Field) {
+    visitor.enter_field(field);
+    for argument in field.arguments.iter().flatten() {
+        walk_input_value(visitor, argument);
+    }
+    walk_type_ref(visitor, &field.field_type);
+    walk_directives(visitor, &field.directives);
+    visitor.leave_field(field);
+}
+
+pub fn walk_input_value<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    input_value: &'ast InputValue,
+) {
+    visitor.enter_input_value(input_value);
+    walk_type_ref(visitor, &input_value.value_type);
+    if let Some(default_value) = &input_value.default_value {
+        walk_value(visitor, default_value);
+    }
+    walk_directives(visitor, &input_value.directives);
+    visitor.leave_input_value(input_value);
+}
+
+pub fn walk_executable_document<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    document: &'ast ExecutableDocument,
+) {
+    visitor.enter_executable_document(document);
+    for definition in &document.definitions {
+        walk_executable_definition(visitor, definition);
+    }
+    visitor.leave_executable_document(document);
+}
+
+pub fn walk_executable_definition<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    definition: &'ast ExecutableDefinition,
+) {
+    visitor.enter_executable_definition(definition);
+    match definition {
+        ExecutableDefinition::Operation(operation) => walk_operation(visitor, operation),
+        ExecutableDefinition::Fragment(fragment) => walk_fragment(visitor, fragment),
+    }
+    visitor.leave_executable_definition(definition);
+}
+
+pub fn walk_operation<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    operation: &'ast OperationDef,
+) {
+    visitor.enter_operation(operation);
+    for variable in &operation.variables {
+        walk_variable_def(visitor, variable);
+    }
+    walk_directives(visitor, &operation.directives);
+    walk_selection_set(visitor, &operation.selection_set);
+    visitor.leave_operation(operation);
+}
+
+pub fn walk_variable_def<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    variable: &'ast VariableDef,
+) {
+    visitor.enter_variable_def(variable);
+    walk_type_ref(visitor, &variable.var_type);
+    if let Some(default_value) = &variable.default_value {
+        walk_value(visitor, default_value);
+    }
+    walk_directives(visitor, &variable.directives);
+    visitor.leave_variable_def(variable);
+}
+
+pub fn walk_fragment<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    fragment: &'ast FragmentDef,
+) {
+    visitor.enter_fragment(fragment);
+    visitor.visit_named_type(&fragment.type_condition);
+    walk_directives(visitor, &fragment.directives);
+    walk_selection_set(visitor, &fragment.selection_set);
+    visitor.leave_fragment(fragment);
+}
+
+fn walk_selection_set<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    selection_set: &'ast [Selection],
+) {
+    for selection in selection_set {
+        walk_selection(visitor, selection);
+    }
+}
+
+pub fn walk_selection<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    selection: &'ast Selection,
+) {
+    visitor.enter_selection(selection);
+    match selection {
+        Selection::Field(field) => walk_field_selection(visitor, field),
+        Selection::FragmentSpread(spread) => walk_fragment_spread(visitor, spread),
+        Selection::InlineFragment(fragment) => walk_inline_fragment(visitor, fragment),
+    }
+    visitor.leave_selection(selection);
+}
+
+pub fn walk_field_selection<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    field: &'ast FieldSelection,
+) {
+    visitor.enter_field_selection(field);
+    for argument in field.arguments.iter().flatten() {
+        walk_argument(visitor, argument);
+    }
+    walk_directives(visitor, &field.directives);
+    walk_selection_set(visitor, &field.selection_set);
+    visitor.leave_field_selection(field);
+}
+
+pub fn walk_fragment_spread<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    spread: &'ast FragmentSpread,
+) {
+    visitor.enter_fragment_spread(spread);
+    walk_directives(visitor, &spread.directives);
+    visitor.leave_fragment_spread(spread);
+}
+
+pub fn walk_inline_fragment<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    fragment: &'ast InlineFragment,
+) {
+    visitor.enter_inline_fragment(fragment);
+    if let Some(type_condition) = &fragment.type_condition {
+        visitor.visit_named_type(type_condition);
+    }
+    walk_directives(visitor, &fragment.directives);
+    walk_selection_set(visitor, &fragment.selection_set);
+    visitor.leave_inline_fragment(fragment);
+}
+
+fn walk_directives<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    directives: &'ast Option<Vec<Directive>>,
+) {
+    for directive in directives.iter().flatten() {
+        walk_directive(visitor, directive);
+    }
+}
+
+pub fn walk_directive<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    directive: &'ast Directive,
+) {
+    visitor.enter_directive(directive);
+    for argument in directive.arguments.iter().flatten() {
+        walk_argument(visitor, argument);
+    }
+    visitor.leave_directive(directive);
+}
+
+pub fn walk_argument<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, argument: &'ast Argument) {
+    visitor.enter_argument(argument);
+    walk_value(visitor, &argument.value);
+    visitor.leave_argument(argument);
+}
+
+pub fn walk_type_ref<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, type_ref: &'ast TypeRef) {
+    visitor.enter_type_ref(type_ref);
+    match type_ref {
+        TypeRef::Named(name) => visitor.visit_named_type(name),
+        TypeRef::NonNull(inner) | TypeRef::List(inner) => walk_type_ref(visitor, inner),
+    }
+    visitor.leave_type_ref(type_ref);
+}
+
+pub fn walk_value<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, value: &'ast Value) {
+    visitor.enter_value(value);
+    match value {
+        Value::List(items) => {
+            for item in items {
+                walk_value(visitor, item);
+            }
+        }
+        Value::Object(fields) => {
+            for (_, field) in fields {
+                walk_value(visitor, field);
+            }
+        }
+        _ => {}
+    }
+    visitor.leave_value(value);
+}
+
+/// Rewrites a document in place. Each method walks the node's children by
+/// default; an override that still wants them walked calls the matching
+/// `walk_*_mut` function, before or after its own changes.
+pub trait VisitMut {
+    fn visit_document_mut(&mut self, document: &mut Document) {
+        walk_document_mut(self, document);
+    }
+
+    fn visit_definition_mut(&mut self, definition: &mut Definition) {
+        walk_definition_mut(self, definition);
+    }
+
+    fn visit_extension_mut(&mut self, extension: &mut Extension) {
+        walk_extension_mut(self, extension);
+    }
+
+    /// Called for object and interface types.
+    fn visit_type_def_mut(&mut self, def: &mut TypeDef) {
+        walk_type_def_mut(self, def);
+    }
+
+    fn visit_scalar_mut(&mut self, scalar: &mut ScalarDef) {
+        walk_directives_mut(self, &mut scalar.directives);
+    }
+
+    fn visit_input_object_mut(&mut self, input: &mut InputObjectDef) {
+        walk_input_object_mut(self, input);
+    }
+
+    fn visit_union_mut(&mut self, union: &mut UnionDef) {
+        walk_union_mut(self, union);
+    }
+
+    fn visit_enum_mut(&mut self, enum_def: &mut EnumDef) {
+        walk_enum_mut(self, enum_def);
+    }
+
+    fn visit_enum_value_mut(&mut self, value: &mut EnumValue) {
+        walk_enum_value_mut(self, value);
+    }
+
+    fn visit_schema_mut(&mut self, schema: &mut SchemaDef) {
+        walk_schema_mut(self, schema);
+    }
+
+    fn visit_root_operation_type_mut(&mut self, root: &mut RootOperationTypeDef) {
+        self.visit_named_type_mut(&mut root.type_name);
+    }
+
+    fn visit_directive_def_mut(&mut self, directive: &mut DirectiveDef) {
+        for argument in directive.arguments.iter_mut().flatten() {
+            self.visit_input_value_mut(argument);
+        }
+    }
+
+    fn visit_field_mut(&mut self, field: &mut Field) {
+        walk_field_mut(self, field);
+    }
+
+    fn visit_input_value_mut(&mut self, input_value: &mut InputValue) {
+        walk_input_value_mut(self, input_value);
+    }
+
+    fn visit_executable_document_mut(&mut self, document: &mut ExecutableDocument) {
+        walk_executable_document_mut(self, document);
+    }
+
+    fn visit_executable_definition_mut(&mut self, definition: &mut ExecutableDefinition) {
+        walk_executable_definition_mut(self, definition);
+    }
+
+    fn visit_operation_mut(&mut self, operation: &mut OperationDef) {
+        walk_operation_mut(self, operation);
+    }
+
+    fn visit_variable_def_mut(&mut self, variable: &mut VariableDef) {
+        walk_variable_def_mut(self, variable);
+    }
+
+    fn visit_fragment_mut(&mut self, fragment: &mut FragmentDef) {
+        walk_fragment_mut(self, fragment);
+    }
+
+    fn visit_selection_mut(&mut self, selection: &mut Selection) {
+        walk_selection_mut(self, selection);
+    }
+
+    fn visit_field_selection_mut(&mut self, field: &mut FieldSelection) {
+        walk_field_selection_mut(self, field);
+    }
+
+    fn visit_fragment_spread_mut(&mut self, spread: &mut FragmentSpread) {
+        walk_directives_mut(self, &mut spread.directives);
+    }
+
+    fn visit_inline_fragment_mut(&mut self, fragment: &mut InlineFragment) {
+        walk_inline_fragment_mut(self, fragment);
+    }
+
+    fn visit_directive_mut(&mut self, directive: &mut Directive) {
+        for argument in directive.arguments.iter_mut().flatten() {
+            self.visit_argument_mut(argument);
+        }
+    }
+
+    fn visit_argument_mut(&mut self, argument: &mut Argument) {
+        self.visit_value_mut(&mut argument.value);
+    }
+
+    fn visit_type_ref_mut(&mut self, type_ref: &mut TypeRef) {
+        walk_type_ref_mut(self, type_ref);
+    }
+
+    /// Called for every reference to a type by name, as
+    /// [`Visitor::visit_named_type`] is.
+    fn visit_named_type_mut(&mut self, _name: &mut String) {}
+
+    fn visit_value_mut(&mut self, value: &mut Value) {
+        walk_value_mut(self, value);
+    }
+}
+
+pub fn walk_document_mut<V: VisitMut + ?Sized>(visitor: &mut V, document: &mut Document) {
+    for definition in &mut document.definitions {
+        visitor.visit_definition_mut(definition);
+    }
+}
+
+pub fn walk_definition_mut<V: VisitMut + ?Sized>(visitor: &mut V, definition: &mut Definition) {
+    match definition {
+        Definition::Type(def) | Definition::Interface(def) => visitor.visit_type_def_mut(def),
+        Definition::Scalar(def) => visitor.visit_scalar_mut(def),
+        Definition::Input(def) => visitor.visit_input_object_mut(def),
+        Definition::Union(def) => visitor.visit_union_mut(def),
+        Definition::Enum(def) => visitor.visit_enum_mut(def),
+        Definition::Schema(def) => visitor.visit_schema_mut(def),
+        Definition::Directive(def) => visitor.visit_directive_def_mut(def),
+        Definition::Extension(extension) => visitor.visit_extension_mut(extension),
+    }
+}
+
+pub fn walk_extension_mut<V: VisitMut + ?Sized>(visitor: &mut V, extension: &mut Extension) {
+    match extension {
+        Extension::Type(def) | Extension::Interface(def) => visitor.visit_type_def_mut(def),
+        Extension::Scalar(def) => visitor.visit_scalar_mut(def),
+        Extension::Input(def) => visitor.visit_input_object_mut(def),
+        Extension::Union(def) => visitor.visit_union_mut(def),
+        Extension::Enum(def) => visitor.visit_enum_mut(def),
+        Extension::Schema(def) => visitor.visit_schema_mut(def),
+    }
+}
+
+pub fn walk_type_def_mut<V: VisitMut + ?Sized>(visitor: &mut V, def: &mut TypeDef) {
+    for interface in &mut def.interfaces {
+        visitor.visit_named_type_mut(interface);
+    }
+    walk_directives_mut(visitor, &mut def.directives);
+    for field in &mut def.fields {
+        visitor.visit_field_mut(field);
+    }
+}
+
+pub fn walk_input_object_mut<V: VisitMut + ?Sized>(visitor: &mut V, input: &mut InputObjectDef) {
+    walk_directives_mut(visitor, &mut input.directives);
+    for field in &mut input.fields {
+        visitor.visit_input_value_mut(field);
+    }
+}
+
+pub fn walk_union_mut<V: VisitMut + ?Sized>(visitor: &mut V, union: &mut UnionDef) {
+    walk_directives_mut(visitor, &mut union.directives);
+    for member in &mut union.members {
+        visitor.visit_type_ref_mut(member);
+    }
+}
+
+pub fn walk_enum_mut<V: VisitMut + ?Sized>(visitor: &mut V, enum_def: &mut EnumDef) {
+    walk_directives_mut(visitor, &mut enum_def.directives);
+    for value in &mut enum_def.values {
+        visitor.visit_enum_value_mut(value);
+    }
+}
+
+pub fn walk_enum_value_mut<V: VisitMut + ?Sized>(visitor: &mut V, value: &mut EnumValue) {
+    walk_directives_mut(visitor, &mut value.directives);
+}
+
+pub fn walk_schema_mut<V: VisitMut + ?Sized>(visitor: &mut V, schema: &mut SchemaDef) {
+    walk_directives_mut(visitor, &mut schema.directives);
+    for root in &mut schema.operation_types {
+        visitor.visit_root_operation_type_mut(root);
+    }
+}
+
+pub fn walk_field_mut<V: VisitMut + ?Sized>(visitor: &mut V, field: &mut Field) {
+    for argument in field.arguments.iter_mut().flatten() {
+        visitor.visit_input_value_mut(argument);
+    }
+    visitor.visit_type_ref_mut(&mut field.field_type);
+    walk_directives_mut(visitor, &mut field.directives);
+}
+
+pub fn walk_input_value_mut<V: VisitMut + ?Sized>(visitor: &mut V, input_value: &mut InputValue) {
+    visitor.visit_type_ref_mut(&mut input_value.value_type);
+    if let Some(default_value) = &mut input_value.default_value {
+        visitor.visit_value_mut(default_value);
+    }
+    walk_directives_mut(visitor, &mut input_value.directives);
+}
+
+pub fn walk_executable_document_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    document: &mut ExecutableDocument,
+) {
+    for definition in &mut document.definitions {
+        visitor.visit_executable_definition_mut(definition);
+    }
+}
+
+pub fn walk_executable_definition_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    definition: &mut ExecutableDefinition,
+) {
+    match definition {
+        ExecutableDefinition::Operation(operation) => visitor.visit_operation_mut(operation),
+        ExecutableDefinition::Fragment(fragment) => visitor.visit_fragment_mut(fragment),
+    }
+}
+
+pub fn walk_operation_mut<V: VisitMut + ?Sized>(visitor: &mut V, operation: &mut OperationDef) {
+    for variable in &mut operation.variables {
+        visitor.visit_variable_def_mut(variable);
+    }
+    walk_directives_mut(visitor, &mut operation.directives);
+    walk_selection_set_mut(visitor, &mut operation.selection_set);
+}
+
+pub fn walk_variable_def_mut<V: VisitMut + ?Sized>(visitor: &mut V, variable: &mut VariableDef) {
+    visitor.visit_type_ref_mut(&mut variable.var_type);
+    if let Some(default_value) = &mut variable.default_value {
+        visitor.visit_value_mut(default_value);
+    }
+    walk_directives_mut(visitor, &mut variable.directives);
+}
+
+pub fn walk_fragment_mut<V: VisitMut + ?Sized>(visitor: &mut V, fragment: &mut FragmentDef) {
+    visitor.visit_named_type_mut(&mut fragment.type_condition);
+    walk_directives_mut(visitor, &mut fragment.directives);
+    walk_selection_set_mut(visitor, &mut fragment.selection_set);
+}
+
+fn walk_selection_set_mut<V: VisitMut + ?Sized>(visitor: &mut V, selection_set: &mut [Selection]) {
+    for selection in selection_set {
+        visitor.visit_selection_mut(selection);
+    }
+}
+
+pub fn walk_selection_mut<V: VisitMut + ?Sized>(visitor: &mut V, selection: &mut Selection) {
+    match selection {
+        Selection::Field(field) => visitor.visit_field_selection_mut(field),
+        Selection::FragmentSpread(spread) => visitor.visit_fragment_spread_mut(spread),
+        Selection::InlineFragment(fragment) => visitor.visit_inline_fragment_mut(fragment),
+    }
+}
+
+pub fn walk_field_selection_mut<V: VisitMut + ?Sized>(visitor: &mut V, field: &mut FieldSelection) {
+    for argument in field.arguments.iter_mut().flatten() {
+        visitor.visit_argument_mut(argument);
+    }
+    walk_directives_mut(visitor, &mut field.directives);
+    walk_selection_set_mut(visitor, &mut field.selection_set);
+}
+
+pub fn walk_inline_fragment_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    fragment: &mut InlineFragment,
+) {
+    if let Some(type_condition) = &mut fragment.type_condition {
+        visitor.visit_named_type_mut(type_condition);
+    }
+    walk_directives_mut(visitor, &mut fragment.directives);
+    walk_selection_set_mut(visitor, &mut fragment.selection_set);
+}
+
+fn walk_directives_mut<V: VisitMut + ?Sized>(
+    visitor: &mut V,
+    directives: &mut Option<Vec<Directive>>,
+) {
+    for directive in directives.iter_mut().flatten() {
+        visitor.visit_directive_mut(directive);
+    }
+}
+
+pub fn walk_type_ref_mut<V: VisitMut + ?Sized>(visitor: &mut V, type_ref: &mut TypeRef) {
+    match type_ref {
+        TypeRef::Named(name) => visitor.visit_named_type_mut(name),
+        TypeRef::NonNull(inner) | TypeRef::List(inner) => visitor.visit_type_ref_mut(inner),
+    }
+}
+
+pub fn walk_value_mut<V: VisitMut + ?Sized>(visitor: &mut V, value: &mut Value) {
+    match value {
+        Value::List(items) => {
+            for item in items {
+                visitor.visit_value_mut(item);
+            }
+        }
+        Value::Object(fields) => {
+            for (_, field) in fields {
+                visitor.visit_value_mut(field);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::{Comments, OperationType, Span};
+    use crate::test_utils::{document, field, input_value, named};
+
+    /// `query Users($limit: Int = 10) { users(first: $limit) { ...UserFields
+    /// ... on User { id } } } fragment UserFields on User { id }`
+    fn executable_document() -> ExecutableDocument {
+        let id = || {
+            Selection::Field(FieldSelection {
+                alias: None,
+                name: "id".to_string(),
+                arguments: None,
+                directives: None,
+                selection_set: Vec::new(),
+                span: Span::default(),
+            })
+        };
+        let users = FieldSelection {
+            alias: None,
+            name: "users".to_string(),
+            arguments: Some(vec![Argument {
+                name: "first".to_string(),
+                value: Value::Variable("limit".to_string()),
+                span: Span::default(),
+            }]),
+            directives: None,
+            selection_set: vec![
+                Selection::FragmentSpread(FragmentSpread {
+                    fragment_name: "UserFields".to_string(),
+                    directives: None,
+                    span: Span::default(),
+                }),
+                Selection::InlineFragment(InlineFragment {
+                    type_condition: Some("User".to_string()),
+                    directives: None,
+                    selection_set: vec![id()],
+                    span: Span::default(),
+                }),
+            ],
+            span: Span::default(),
+        };
+
+        ExecutableDocument {
+            definitions: vec![
+                ExecutableDefinition::Operation(OperationDef {
+                    operation: OperationType::Query,
+                    name: Some("Users".to_string()),
+                    variables: vec![VariableDef {
+                        name: "limit".to_string(),
+                        var_type: named("Int"),
+                        default_value: Some(Value::Int(10)),
+                        directives: None,
+                        span: Span::default(),
+                    }],
+                    directives: None,
+                    selection_set: vec![Selection::Field(users)],
+                    span: Span::default(),
+                }),
+                ExecutableDefinition::Fragment(FragmentDef {
+                    name: "UserFields".to_string(),
+                    type_condition: "User".to_string(),
+                    directives: None,
+                    selection_set: vec![id()],
+                    span: Span::default(),
+                }),
+            ],
+        }
+    }
+
+    #[derive(Default)]
+    struct Recorder<'ast> {
+        events: Vec<String>,
+        named_types: Vec<&'ast str>,
+        values: usize,
+    }
+
+    impl<'ast> Visitor<'ast> for Recorder<'ast> {
+        fn enter_object(&mut self, object: &'ast TypeDef) {
+            self.events.push(format!("enter {}", object.name));
+        }
+
+        fn leave_object(&mut self, object: &'ast TypeDef) {
+            self.events.push(format!("leave {}", object.name));
+        }
+
+        fn enter_interface(&mut self, interface: &'ast TypeDef) {
+            self.events.push(format!("interface {}", interface.name));
+        }
+
+        fn enter_union(&mut self, union: &'ast UnionDef) {
+            self.events.push(format!("union {}", union.name));
+        }
+
+        fn enter_field(&mut self, field: &'ast Field) {
+            self.events.push(format!("field {}", field.name));
+        }
+
+        fn enter_input_value(&mut self, input_value: &'ast InputValue) {
+            self.events.push(format!("input {}", input_value.name));
+        }
+
+        fn enter_operation(&mut self, operation: &'ast OperationDef) {
+            self.events.push(format!(
+                "operation {}",
+                operation.name.as_deref().unwrap_or_default()
+            ));
+        }
+
+        fn enter_fragment(&mut self, fragment: &'ast FragmentDef) {
+            self.events.push(format!("fragment {}", fragment.name));
+        }
+
+        fn enter_field_selection(&mut self, field: &'ast FieldSelection) {
+            self.events.push(format!("select {}", field.name));
+        }
+
+        fn enter_fragment_spread(&mut self, spread: &'ast FragmentSpread) {
+            self.events.push(format!("spread {}", spread.fragment_name));
+        }
+
+        fn leave_inline_fragment(&mut self, _fragment: &'ast InlineFragment) {
+            self.events.push("leave inline fragment".to_string());
+        }
+
+        fn visit_named_type(&mut self, name: &'ast str) {
+            self.named_types.push(name);
+        }
+
+        fn enter_value(&mut self, _value: &'ast Value) {
+            self.values += 1;
+        }
+    }
+
+    #[test]
+    fn test_visitor_order() {
+        let document = document();
+        let mut recorder = Recorder::default();
+        walk_document(&mut recorder, &document);
+
+        assert_eq!(
+            recorder.events,
+            vec![
+                "interface Node",
+                "field id",
+                "interface Entity",
+                "field id",
+                "enter User",
+                "field id",
+                "leave User",
+                "enter Post",
+                "field id",
+                "leave Post",
+                "union SearchResult",
+                "enter Query",
+                "field users",
+                "field node",
+                "leave Query",
+                "input id",
+            ]
+        );
+        assert_eq!(
+            recorder.named_types,
+            vec![
+                "ID", "Node", "ID", "Entity", "Node", "ID", "Node", "ID", "User", "Post", "User",
+                "Node", "ID",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_visitor_walks_executable_documents() {
+        let document = executable_document();
+        let mut recorder = Recorder::default();
+        walk_executable_document(&mut recorder, &document);
+
+        assert_eq!(
+            recorder.events,
+            vec![
+                "operation Users",
+                "select users",
+                "spread UserFields",
+                "select id",
+                "leave inline fragment",
+                "fragment UserFields",
+                "select id",
+            ]
+        );
+        assert_eq!(recorder.named_types, vec!["Int", "User", "User"]);
+        assert_eq!(recorder.values, 2);
+    }
+
+    /// Renames the type `User` to `Account` wherever it is defined or
+    /// referenced, and the root query type to `Root`.
+    struct Rename;
+
+    impl VisitMut for Rename {
+        fn visit_type_def_mut(&mut self, def: &mut TypeDef) {
+            if let Some(name) = renamed(&def.name) {
+                def.name = name;
+            }
+            walk_type_def_mut(self, def);
+        }
+
+        fn visit_named_type_mut(&mut self, name: &mut String) {
+            if let Some(renamed) = renamed(name) {
+                *name = renamed;
+            }
+        }
+
+        fn visit_value_mut(&mut self, value: &mut Value) {
+            if let Value::Int(i) = value {
+                *i *= 10;
+            }
+            walk_value_mut(self, value);
+        }
+    }
+
+    fn renamed(name: &str) -> Option<String> {
+        match name {
+            "User" => Some("Account".to_string()),
+            "Query" => Some("Root".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_visit_mut_renames_types() {
+        let mut document = document();
+        document.definitions.push(Definition::Schema(SchemaDef {
+            operation_types: vec![RootOperationTypeDef {
+                operation: OperationType::Query,
+                type_name: "Query".to_string(),
+                span: Span::default(),
+                comments: Comments::default(),
+            }],
+            directives: None,
+            description: None,
+            span: Span::default(),
+            comments: Comments::default(),
+        }));
+        document
+            .definitions
+            .push(Definition::Extension(Extension::Interface(TypeDef {
+                name: "Profile".to_string(),
+                interfaces: vec!["User".to_string()],
+                fields: vec![Field {
+                    arguments: Some(vec![input_value(
+                        "sizes",
+                        named("Int"),
+                        Some(Value::List(vec![Value::Int(1), Value::Int(2)])),
+                    )]),
+                    ..field("avatar", named("String"))
+                }],
+                ..TypeDef::default()
+            })));
+        Rename.visit_document_mut(&mut document);
+
+        let names: Vec<Option<&str>> = document.definitions.iter().map(Definition::name).collect();
+        assert_eq!(
+            names,
+            vec![
+                Some("Node"),
+                Some("Entity"),
+                Some("Account"),
+                Some("Post"),
+                Some("SearchResult"),
+                Some("Root"),
+                Some("Filter"),
+                None,
+                None,
+            ]
+        );
+        let Definition::Union(search) = &document.definitions[4] else {
+            panic!("Expected a union");
+        };
+        assert_eq!(search.members[0], named("Account"));
+        let Definition::Type(query) = &document.definitions[5] else {
+            panic!("Expected a type");
+        };
+        assert_eq!(query.fields[0].field_type.named_type(), "Account");
+        assert_eq!(
+            document.root_operation_type(OperationType::Query),
+            Some("Root")
+        );
+
+        let Definition::Extension(Extension::Interface(profile)) = &document.definitions[8] else {
+            panic!("Expected an interface extension");
+        };
+        assert_eq!(profile.interfaces, vec!["Account"]);
+        assert_eq!(
+            profile.fields[0].arguments.as_ref().unwrap()[0].default_value,
+            Some(Value::List(vec![Value::Int(10), Value::Int(20)]))
+        );
+    }
+
+    #[test]
+    fn test_visit_mut_rewrites_executable_documents() {
+        let mut document = executable_document();
+        Rename.visit_executable_document_mut(&mut document);
+
+        let ExecutableDefinition::Operation(operation) = &document.definitions[0] else {
+            panic!("Expected an operation");
+        };
+        assert_eq!(operation.variables[0].default_value, Some(Value::Int(100)));
+        let Selection::Field(users) = &operation.selection_set[0] else {
+            panic!("Expected a field");
+        };
+        let Selection::InlineFragment(inline) = &users.selection_set[1] else {
+            panic!("Expected an inline fragment");
+        };
+        assert_eq!(inline.type_condition.as_deref(), Some("Account"));
+
+        let ExecutableDefinition::Fragment(fragment) = &document.definitions[1] else {
+            panic!("Expected a fragment");
+        };
+        assert_eq!(fragment.type_condition, "Account");
+    }
+}